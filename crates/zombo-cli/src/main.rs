@@ -1,12 +1,18 @@
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
+use notify::{RecursiveMode, Watcher};
 use std::{
+    error::Error,
+    fmt::Display,
     fs, io,
     path::{Path, PathBuf},
+    process::ExitCode,
+    sync::mpsc,
+    time::Duration,
 };
 use zombo::{
     model::{Item, Stat},
     table::Table,
-    Zomboid,
+    GroupField, Report, Zomboid,
 };
 
 #[derive(Parser, Debug)]
@@ -15,10 +21,39 @@ use zombo::{
 struct Args {
     path: PathBuf,
 
+    /// Output format of `list` and `describe` commands
+    #[arg(short, long, value_enum, global = true, default_value_t = Format::Table)]
+    format: Format,
+
     #[command(subcommand)]
     cmd: Command,
 }
 
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    /// Box-drawn table
+    Table,
+    /// JSON array of the rows
+    Json,
+    /// CSV with a header row
+    Csv,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+enum By {
+    Condition,
+    Type,
+}
+
+impl From<By> for GroupField {
+    fn from(v: By) -> Self {
+        match v {
+            By::Condition => Self::Condition,
+            By::Type => Self::Type,
+        }
+    }
+}
+
 #[derive(Subcommand, Debug)]
 enum Command {
     List {
@@ -27,20 +62,135 @@ enum Command {
 
         #[arg(short, long)]
         skip: Option<usize>,
+
+        /// Show only items with amount below the threshold
+        #[arg(short, long)]
+        below: Option<u32>,
+
+        /// Re-render the table whenever the data changes on disk
+        #[arg(short, long)]
+        watch: bool,
     },
-    Describe,
+    Describe {
+        /// Field of the items to calculate percentages per
+        #[arg(long, value_enum, default_value_t = By::Condition)]
+        by: By,
+    },
+    /// Print condition percentages, type totals and amount summary
+    Stats,
+    /// Print amount of rows and a hash of them per file
+    Manifest,
+}
+
+/// Items of a single file in the directory, see [`zombo::read_file`].
+type Source = Box<dyn Iterator<Item = Result<Item, FileError>>>;
+
+/// Error of reading an item of the file at `path`.
+#[derive(Debug)]
+struct FileError {
+    path: PathBuf,
+    source: zombo::Error,
+}
+
+impl Display for FileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.path.display(), self.source)
+    }
+}
+
+impl Error for FileError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+/// Opens the file at `path` with [`zombo::read_file`] adding the path
+/// to the errors of opening the file and reading its items.
+fn open(path: &Path) -> Result<Source, Box<dyn Error>> {
+    let items = zombo::read_file(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let path = path.to_path_buf();
+    Ok(Box::new(items.map(move |v| {
+        v.map_err(|source| FileError {
+            path: path.clone(),
+            source,
+        })
+    })))
+}
+
+/// Read all files in `path` directory and return a Vector of item iterators.
+/// Parser of each file is chosen by its extension: `.json` and `.ndjson` files
+/// are read as JSON and the rest ones as CSV. With `gzip` feature `.gz` files,
+/// e.g. `data.csv.gz`, are decompressed first. The directory must contain only
+/// files, otherwise reading its iterator will return an error.
+///
+/// Header of each CSV file is checked up front, see [`check_header`].
+fn dir_to_readers(path: impl AsRef<Path>) -> Result<Vec<Source>, Box<dyn Error>> {
+    let path = path.as_ref();
+    let paths = dir_paths(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    for v in &paths {
+        check_header(v)?;
+    }
+    paths.iter().map(|v| open(v)).collect()
+}
+
+/// Columns every CSV file must have. Others, e.g. `added`, are allowed.
+const EXPECTED_HEADER: [&str; 5] = ["id", "name", "type", "condition", "amount"];
+
+/// Returns an error naming the file at `path` if its header lacks any
+/// of [`EXPECTED_HEADER`] columns. Files whose header can't be read are
+/// left to fail on reading their items.
+fn check_header(path: &Path) -> Result<(), Box<dyn Error>> {
+    let Ok(Some(header)) = zombo::read_header(path) else {
+        return Ok(());
+    };
+    if EXPECTED_HEADER
+        .iter()
+        .all(|v| header.iter().any(|h| h == v))
+    {
+        return Ok(());
+    }
+    Err(format!(
+        "{}: unexpected header `{}`, expected `{}`",
+        path.display(),
+        header.join(","),
+        EXPECTED_HEADER.join(",")
+    )
+    .into())
 }
 
-/// Read all files in `path` directory and return a Vector of [csv::Reader] objects.
-/// The directory must contain only `.csv` files, otherwise the function will
-/// return an error.
-fn dir_to_readers(path: impl AsRef<Path>) -> io::Result<Vec<csv::Reader<fs::File>>> {
-    let mut vec = Vec::new();
+/// Paths of all entries in `path` directory sorted by name,
+/// so the order of items is stable between runs.
+fn dir_paths(path: impl AsRef<Path>) -> io::Result<Vec<PathBuf>> {
+    let mut paths = Vec::new();
     for entry in fs::read_dir(path)? {
-        let entry = entry?;
-        vec.push(csv::Reader::from_path(entry.path())?);
+        paths.push(entry?.path());
+    }
+    paths.sort();
+    Ok(paths)
+}
+
+/// Builds a table of file name, amount of rows and their hash for
+/// the file in `path` or each file in the directory, see [`zombo::manifest`].
+fn manifest_table(path: &Path) -> Result<Table<Vec<String>>, Box<dyn Error>> {
+    let paths = match path.is_file() {
+        true => vec![path.to_path_buf()],
+        false => dir_paths(path).map_err(|e| format!("{}: {}", path.display(), e))?,
+    };
+    let mut readers = Vec::with_capacity(paths.len());
+    for v in paths {
+        let name = v
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string();
+        readers.push((name, open(&v)?));
     }
-    Ok(vec)
+
+    let rows = zombo::manifest(readers)?
+        .into_iter()
+        .map(|(name, rows, hash)| vec![name, rows.to_string(), hash])
+        .collect();
+    Ok(Table::new(rows).with_header(["FILE", "ROWS", "HASH"]))
 }
 
 /// Helper enumeration that allows handling either a single `.csv` file
@@ -54,62 +204,166 @@ impl<S, D, E> ZomboIter<S, D>
 where
     S: Iterator<Item = Result<Item, E>>,
     D: Iterator<Item = Result<Item, E>>,
-    E: std::error::Error,
+    E: Error,
 {
-    fn list_table(&mut self, take: Option<usize>, skip: Option<usize>) -> Result<Table<Item>, E> {
+    fn list_table(
+        &mut self,
+        take: Option<usize>,
+        skip: Option<usize>,
+        below: Option<u32>,
+    ) -> Result<Table<Item>, E> {
         match self {
             Self::Single(z) => {
                 z.set_take(take);
                 z.set_skip(skip);
+                z.set_below(below);
                 z.stream()
             }
             Self::Dir(z) => {
                 z.set_take(take);
                 z.set_skip(skip);
+                z.set_below(below);
                 z.stream()
             }
         }
     }
 
-    fn describe_table(&mut self) -> Result<Table<Stat>, E> {
+    fn describe_table(&mut self, by: By) -> Result<Table<Stat>, E> {
+        match self {
+            Self::Single(z) => z.describe_by(by.into()),
+            Self::Dir(z) => z.describe_by(by.into()),
+        }
+    }
+
+    fn report(&mut self) -> Result<Report, E> {
         match self {
-            Self::Single(z) => z.describe(),
-            Self::Dir(z) => z.describe(),
+            Self::Single(z) => z.report(),
+            Self::Dir(z) => z.report(),
         }
     }
 }
 
-fn main() {
-    let args = Args::parse();
+/// Events coming within this interval after a change are
+/// coalesced into a single re-render.
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(200);
 
-    // Using readers Vec we ensure that Readers aren't dropped
-    // until iterators aren't read.
-    let mut readers = Vec::<csv::Reader<fs::File>>::new();
+/// Read the data in `path` and render the output of `cmd`.
+fn render(path: &Path, cmd: &Command, format: Format) -> Result<String, Box<dyn Error>> {
+    if let Command::Manifest = cmd {
+        return Ok(manifest_table(path)?.to_string());
+    }
 
-    let mut zombo = if args.path.is_file() {
-        readers.push(
-            csv::Reader::from_path(args.path.as_path()).expect("Couldn't create a CSV reader."),
-        );
-        ZomboIter::Single(Zomboid::new(readers[0].deserialize()))
+    let mut zombo = if path.is_file() {
+        ZomboIter::Single(Zomboid::new(open(path)?))
     } else {
-        readers = dir_to_readers(args.path).expect("Couldn't read directory.");
-        ZomboIter::Dir(Zomboid::new(
-            readers.iter_mut().flat_map(|it| it.deserialize::<Item>()),
-        ))
+        let readers = dir_to_readers(path)?;
+        ZomboIter::Dir(Zomboid::new(readers.into_iter().flatten()))
     };
 
-    match args.cmd {
-        Command::List { take, skip } => {
-            let table = zombo
-                .list_table(take, skip)
-                .expect("Couldn't list CSV data.");
-            println!("{table}");
+    let out = match cmd {
+        Command::List {
+            take, skip, below, ..
+        } => {
+            let table = zombo.list_table(*take, *skip, *below)?;
+            match format {
+                Format::Table => table.to_string(),
+                Format::Json => serde_json::to_string_pretty(table.as_data())?,
+                Format::Csv => {
+                    let mut out = Vec::new();
+                    table.to_csv(&mut out)?;
+                    String::from_utf8(out)?
+                }
+            }
+        }
+        Command::Describe { by } => {
+            let table = zombo.describe_table(*by)?;
+            match format {
+                Format::Table => table.to_string(),
+                Format::Json => serde_json::to_string_pretty(table.as_data())?,
+                Format::Csv => {
+                    let mut w = csv::Writer::from_writer(Vec::new());
+                    for v in table.as_data() {
+                        w.serialize(v)?;
+                    }
+                    String::from_utf8(w.into_inner()?)?
+                }
+            }
+        }
+        Command::Stats => {
+            let report = zombo.report()?;
+            format!(
+                "Condition percentages\n{}\n\nType totals\n{}\n\nAmount summary\n{}",
+                report.conditions, report.types, report.amounts
+            )
+        }
+        Command::Manifest => unreachable!("manifest is rendered from the files"),
+    };
+    Ok(out)
+}
+
+/// Call `render` once and then again on every change in `path`,
+/// until `render` returns `false`.
+fn watch(path: &Path, debounce: Duration, render: impl FnMut() -> bool) -> notify::Result<()> {
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx)?;
+    watcher.watch(path, RecursiveMode::NonRecursive)?;
+
+    rerender_on(&rx, debounce, render)
+}
+
+/// Call `render` once and then again on every event received from `rx`,
+/// until `render` returns `false` or `rx` is disconnected.
+///
+/// Events following each other within `debounce` cause a single render.
+fn rerender_on(
+    rx: &mpsc::Receiver<notify::Result<notify::Event>>,
+    debounce: Duration,
+    mut render: impl FnMut() -> bool,
+) -> notify::Result<()> {
+    if !render() {
+        return Ok(());
+    }
+    while let Ok(event) = rx.recv() {
+        event?;
+        while rx.recv_timeout(debounce).is_ok() {}
+
+        if !render() {
+            break;
         }
-        Command::Describe => {
-            let table = zombo.describe_table().expect("Couldn't describe CSV data.");
-            println!("{table}");
+    }
+    Ok(())
+}
+
+fn run(args: &Args) -> Result<(), Box<dyn Error>> {
+    match args.cmd {
+        Command::List { watch: true, .. } => {
+            watch(&args.path, WATCH_DEBOUNCE, || {
+                // Clear the screen and move cursor to the top left corner
+                print!("\x1b[2J\x1b[H");
+                // Keep watching, the data may be fixed by the next change
+                match render(&args.path, &args.cmd, args.format) {
+                    Ok(out) => println!("{}", out),
+                    Err(e) => eprintln!("error: {}", e),
+                }
+                true
+            })
+            .map_err(|e| format!("{}: {}", args.path.display(), e))?;
         }
+        _ => println!("{}", render(&args.path, &args.cmd, args.format)?),
     };
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+
+    match run(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
 }
 
 #[cfg(test)]
@@ -161,10 +415,29 @@ mod tests {
         assert_eq!(rdrs.len(), 2);
 
         for mut v in rdrs {
-            assert!(v.headers().is_ok());
+            assert!(v.next().unwrap().is_ok());
         }
     }
 
+    #[test]
+    fn readers_vec_mismatched_header() {
+        let dir = tempdir().unwrap();
+        let f1 = dir.path().join("f1.csv");
+        let f2 = dir.path().join("f2.csv");
+
+        setup_csv_file(&f1).unwrap();
+        fs::write(
+            &f2,
+            "id,title,type,condition,amount\n1,Hummer,Tool,Mint,10\n",
+        )
+        .unwrap();
+
+        let err = dir_to_readers(dir.path()).err().unwrap().to_string();
+        assert!(err.contains("f2.csv"));
+        assert!(!err.contains("f1.csv"));
+        assert!(err.contains("id,title,type,condition,amount"));
+    }
+
     #[test]
     fn readers_vec_non_csv() {
         let dir = tempdir().unwrap();
@@ -177,11 +450,287 @@ mod tests {
         let readers = dir_to_readers(dir.path()).unwrap();
         let mut has_error = false;
         for mut v in readers {
-            has_error = v.headers().is_err();
+            has_error = v.next().is_some_and(|v| v.is_err());
             if has_error {
                 break;
             }
         }
         assert!(has_error);
     }
+
+    #[test]
+    fn readers_vec_mixed_formats() {
+        let dir = tempdir().unwrap();
+        setup_csv_file(dir.path().join("f1.csv")).unwrap();
+        fs::write(
+            dir.path().join("f2.ndjson"),
+            "{\"id\": 5, \"name\": \"Rope\", \"type\": \"Tool\", \"condition\": \"Worn\", \"amount\": 3}\n\
+             {\"id\": 6, \"name\": \"Tape\", \"type\": \"Tool\", \"condition\": \"New\", \"amount\": 7}\n",
+        )
+        .unwrap();
+
+        let readers = dir_to_readers(dir.path()).unwrap();
+        let mut zombo =
+            ZomboIter::<std::iter::Empty<_>, _>::Dir(Zomboid::new(readers.into_iter().flatten()));
+        let table = zombo.list_table(None, None, None).unwrap();
+
+        assert_eq!(table.as_data().len(), 5 + 2);
+    }
+
+    #[test]
+    fn render_row_error() {
+        let args = Args::try_parse_from(["zombo-cli", "data", "list"]).unwrap();
+
+        let dir = tempdir().unwrap();
+        setup_csv_file(dir.path().join("f1.csv")).unwrap();
+        fs::write(
+            dir.path().join("f2.csv"),
+            "id,name,type,condition,amount\n5,Rope,Tool,Worn,many\n",
+        )
+        .unwrap();
+
+        let e = render(dir.path(), &args.cmd, args.format)
+            .unwrap_err()
+            .to_string();
+        assert!(e.contains("f2.csv: row 1:"), "{e}");
+    }
+
+    #[test]
+    fn list_formats() {
+        let dir = tempdir().unwrap();
+        let f = dir.path().join("f.csv");
+        setup_csv_file(&f).unwrap();
+        let render_as = |cmd: &str, format: &str| {
+            let args =
+                Args::try_parse_from(["zombo-cli", "data.csv", cmd, "--format", format]).unwrap();
+            render(&f, &args.cmd, args.format).unwrap()
+        };
+
+        let out = render_as("list", "table");
+        assert!(out.starts_with('┌'));
+
+        let out = render_as("list", "json");
+        let items: Vec<Item> = serde_json::from_str(&out).unwrap();
+        assert_eq!(items.len(), 5);
+        assert_eq!(items[0].name, "Hummer");
+
+        let out = render_as("list", "csv");
+        let mut lines = out.lines();
+        assert_eq!(lines.next(), Some("id,name,type,condition,amount"));
+        assert_eq!(lines.next(), Some("1,Hummer,Tool,Mint,10"));
+        assert_eq!(lines.count(), 4);
+    }
+
+    #[test]
+    fn describe_formats() {
+        let dir = tempdir().unwrap();
+        let f = dir.path().join("f.csv");
+        setup_csv_file(&f).unwrap();
+        let args =
+            Args::try_parse_from(["zombo-cli", "-f", "json", "data.csv", "describe"]).unwrap();
+        let out = render(&f, &args.cmd, args.format).unwrap();
+
+        let stats: Vec<serde_json::Value> = serde_json::from_str(&out).unwrap();
+        assert_eq!(stats.len(), 3);
+        assert!(stats
+            .iter()
+            .all(|v| v["name"].is_string() && v["value"].is_f64()));
+
+        let args =
+            Args::try_parse_from(["zombo-cli", "data.csv", "describe", "-f", "csv"]).unwrap();
+        let out = render(&f, &args.cmd, args.format).unwrap();
+        assert!(out.starts_with("name,value\n"));
+        assert_eq!(out.lines().count(), 1 + 3);
+    }
+
+    #[test]
+    fn describe_by_type() {
+        let dir = tempdir().unwrap();
+        let f = dir.path().join("f.csv");
+        setup_csv_file(&f).unwrap();
+        let args =
+            Args::try_parse_from(["zombo-cli", "data.csv", "describe", "--by", "type"]).unwrap();
+        let out = render(&f, &args.cmd, args.format).unwrap();
+
+        assert!(out.contains("TYPE"));
+        assert!(out.contains("Tool"));
+    }
+
+    #[test]
+    fn list_below() {
+        let args =
+            Args::try_parse_from(["zombo-cli", "data.csv", "list", "--below", "10"]).unwrap();
+        let Command::List {
+            take, skip, below, ..
+        } = args.cmd
+        else {
+            panic!("Expected list command");
+        };
+
+        let dir = tempdir().unwrap();
+        let f = dir.path().join("f.csv");
+        setup_csv_file(&f).unwrap();
+
+        let mut r = csv::Reader::from_path(&f).unwrap();
+        let mut zombo = ZomboIter::<_, std::iter::Empty<_>>::Single(Zomboid::new(r.deserialize()));
+        let table = zombo.list_table(take, skip, below).unwrap();
+        let names: Vec<&str> = table.as_data().iter().map(|v| v.name.as_str()).collect();
+
+        assert_eq!(names, vec!["Garden saw", "Metal saw"]);
+    }
+
+    #[test]
+    fn list_rerender() {
+        let dir = tempdir().unwrap();
+        let f = dir.path().join("f.csv");
+        setup_csv_file(&f).unwrap();
+
+        let cmd = Command::List {
+            take: None,
+            skip: None,
+            below: None,
+            watch: true,
+        };
+        // A burst of events is debounced into a single render
+        let (tx, rx) = mpsc::channel();
+        tx.send(Ok(notify::Event::default())).unwrap();
+        tx.send(Ok(notify::Event::default())).unwrap();
+        drop(tx);
+
+        let mut outs = Vec::new();
+        rerender_on(&rx, Duration::from_secs(60), || {
+            outs.push(render(&f, &cmd, Format::Table).unwrap());
+            let mut file = fs::OpenOptions::new().append(true).open(&f).unwrap();
+            io::Write::write_all(&mut file, b"5,Crowbar,Tool,Worn,1\n").unwrap();
+            true
+        })
+        .unwrap();
+
+        assert_eq!(outs.len(), 2);
+        assert!(!outs[0].contains("Crowbar"));
+        assert!(outs[1].contains("Crowbar"));
+
+        // Rendering stops once `render` returns `false`
+        let (tx, rx) = mpsc::channel();
+        tx.send(Ok(notify::Event::default())).unwrap();
+        let mut renders = 0;
+        rerender_on(&rx, Duration::ZERO, || {
+            renders += 1;
+            false
+        })
+        .unwrap();
+        assert_eq!(renders, 1);
+
+        let (tx, rx) = mpsc::channel();
+        tx.send(Err(notify::Error::generic("lost"))).unwrap();
+        assert!(rerender_on(&rx, Duration::ZERO, || true).is_err());
+    }
+
+    #[test]
+    #[ignore = "depends on filesystem notifications of the host"]
+    fn list_watch() {
+        let dir = tempdir().unwrap();
+        let f = dir.path().join("f.csv");
+        setup_csv_file(&f).unwrap();
+
+        let cmd = Command::List {
+            take: None,
+            skip: None,
+            below: None,
+            watch: true,
+        };
+        let (tx, rx) = mpsc::channel();
+        let path = f.clone();
+        let handle = std::thread::spawn(move || {
+            let mut renders = 0;
+            watch(&path, Duration::from_millis(50), || {
+                renders += 1;
+                tx.send(render(&path, &cmd, Format::Table).unwrap())
+                    .unwrap();
+                renders < 2
+            })
+            .unwrap();
+        });
+
+        let first = rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert!(!first.contains("Crowbar"));
+
+        let mut file = fs::OpenOptions::new().append(true).open(&f).unwrap();
+        io::Write::write_all(&mut file, b"5,Crowbar,Tool,Worn,1\n").unwrap();
+        drop(file);
+
+        let second = rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert!(second.contains("Crowbar"));
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn stats() {
+        let args = Args::try_parse_from(["zombo-cli", "data.csv", "stats"]).unwrap();
+
+        let dir = tempdir().unwrap();
+        let f = dir.path().join("f.csv");
+        setup_csv_file(&f).unwrap();
+
+        let out = render(&f, &args.cmd, args.format).unwrap();
+
+        for heading in ["Condition percentages", "Type totals", "Amount summary"] {
+            assert!(out.contains(heading));
+        }
+        assert!(out.contains("MEDIAN"));
+    }
+
+    #[test]
+    fn manifest() {
+        let args = Args::try_parse_from(["zombo-cli", "data", "manifest"]).unwrap();
+
+        let dir = tempdir().unwrap();
+        setup_csv_file(dir.path().join("f1.csv")).unwrap();
+        let mut writer = Writer::from_path(dir.path().join("f2.csv")).unwrap();
+        writer
+            .write_record(["id", "name", "type", "condition", "amount"])
+            .unwrap();
+        writer
+            .write_record(["5", "Crowbar", "Tool", "Worn", "1"])
+            .unwrap();
+        drop(writer);
+
+        let table = manifest_table(dir.path()).unwrap();
+        let rows: Vec<(&str, &str)> = table
+            .as_data()
+            .iter()
+            .map(|v| (v[0].as_str(), v[1].as_str()))
+            .collect();
+        assert_eq!(rows, vec![("f1.csv", "5"), ("f2.csv", "1")]);
+
+        let hashes: Vec<&str> = table.as_data().iter().map(|v| v[2].as_str()).collect();
+        assert_ne!(hashes[0], hashes[1]);
+        assert_eq!(
+            render(dir.path(), &args.cmd, args.format).unwrap(),
+            render(dir.path(), &args.cmd, args.format).unwrap()
+        );
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn list_gzip() {
+        use flate2::{write::GzEncoder, Compression};
+
+        let args = Args::try_parse_from(["zombo-cli", "data.csv.gz", "list"]).unwrap();
+
+        let dir = tempdir().unwrap();
+        let csv = dir.path().join("f.csv");
+        setup_csv_file(&csv).unwrap();
+        let gz = dir.path().join("f.csv.gz");
+        let mut encoder = GzEncoder::new(fs::File::create(&gz).unwrap(), Compression::default());
+        io::Write::write_all(&mut encoder, &fs::read(&csv).unwrap()).unwrap();
+        encoder.finish().unwrap();
+        fs::remove_file(&csv).unwrap();
+
+        let out = render(&gz, &args.cmd, args.format).unwrap();
+        assert!(out.contains("Garden saw") && out.contains("Metal saw"));
+
+        let out = render(dir.path(), &args.cmd, args.format).unwrap();
+        assert!(out.contains("Hummer"));
+    }
 }
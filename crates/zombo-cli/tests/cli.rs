@@ -0,0 +1,14 @@
+use std::process::Command;
+
+#[test]
+fn missing_path_fails() {
+    let out = Command::new(env!("CARGO_BIN_EXE_zombo-cli"))
+        .args(["path/that/does/not/exist.csv", "list"])
+        .output()
+        .unwrap();
+
+    assert_eq!(out.status.code(), Some(1));
+    let stderr = String::from_utf8(out.stderr).unwrap();
+    assert!(stderr.starts_with("error: path/that/does/not/exist.csv"));
+    assert!(!stderr.contains("panicked"));
+}
@@ -0,0 +1,106 @@
+//! Derive macro for the `zombo::table::RowDisplay` trait.
+//!
+//! Every named field of the struct becomes a cell rendered with its
+//! [`Display`](std::fmt::Display) implementation. Cells share the table width
+//! evenly and are centered unless the field is marked with
+//! `#[row(align = "left")]` or `#[row(align = "right")]`.
+//!
+//! ```ignore
+//! use zombo::table::RowDisplay;
+//!
+//! #[derive(RowDisplay)]
+//! struct Row {
+//!     #[row(align = "right")]
+//!     id: usize,
+//!     name: String,
+//! }
+//! ```
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Fields, LitStr};
+
+#[proc_macro_derive(RowDisplay, attributes(row))]
+pub fn derive_row_display(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    &input.ident,
+                    "RowDisplay can be derived only for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "RowDisplay can be derived only for structs",
+            ))
+        }
+    };
+
+    let mut cells = Vec::new();
+    let mut alignments = Vec::new();
+    for field in fields {
+        let ident = &field.ident;
+        cells.push(quote!(self.#ident.to_string()));
+        alignments.push(alignment(field)?);
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::zombo::table::RowDisplay for #name #ty_generics #where_clause {
+            fn to_row(&self, table_width: usize) -> String {
+                ::zombo::table::format_cells(
+                    &::zombo::table::RowDisplay::cells(self),
+                    table_width,
+                    &::zombo::table::RowDisplay::alignments(self),
+                )
+            }
+
+            fn cells(&self) -> Vec<String> {
+                vec![#(#cells),*]
+            }
+
+            fn alignments(&self) -> Vec<::zombo::table::Alignment> {
+                vec![#(::zombo::table::Alignment::#alignments),*]
+            }
+        }
+    })
+}
+
+/// Reads alignment of the field from `#[row(align = "...")]`, it's centered by default.
+fn alignment(field: &syn::Field) -> syn::Result<syn::Ident> {
+    let mut align = syn::Ident::new("Center", proc_macro2::Span::call_site());
+    for attr in field.attrs.iter().filter(|a| a.path().is_ident("row")) {
+        attr.parse_nested_meta(|meta| {
+            if !meta.path.is_ident("align") {
+                return Err(meta.error("unsupported row attribute, expected `align`"));
+            }
+            let value: LitStr = meta.value()?.parse()?;
+            let variant = match value.value().as_str() {
+                "left" => "Left",
+                "center" => "Center",
+                "right" => "Right",
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        value,
+                        "expected one of \"left\", \"center\", \"right\"",
+                    ))
+                }
+            };
+            align = syn::Ident::new(variant, value.span());
+            Ok(())
+        })?;
+    }
+    Ok(align)
+}
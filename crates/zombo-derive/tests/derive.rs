@@ -0,0 +1,62 @@
+use zombo::table::{cell_widths, RowDisplay, Table};
+
+#[derive(RowDisplay)]
+struct Derived {
+    id: usize,
+    name: String,
+}
+
+struct Manual {
+    id: usize,
+    name: String,
+}
+
+impl RowDisplay for Manual {
+    fn to_row(&self, table_width: usize) -> String {
+        let (width, last) = cell_widths(table_width, 2);
+        format!("│ {:^width$} │ {:^last$} │", self.id, self.name)
+    }
+
+    fn cells(&self) -> Vec<String> {
+        vec![self.id.to_string(), self.name.clone()]
+    }
+}
+
+#[derive(RowDisplay)]
+struct Aligned {
+    #[row(align = "right")]
+    id: usize,
+    #[row(align = "left")]
+    name: &'static str,
+}
+
+#[test]
+fn derived_matches_manual() {
+    let derived = Derived {
+        id: 1,
+        name: "Hummer".into(),
+    };
+    let manual = Manual {
+        id: 1,
+        name: "Hummer".into(),
+    };
+
+    for width in [20, 41, 100] {
+        assert_eq!(derived.to_row(width), manual.to_row(width));
+    }
+    assert_eq!(derived.cells(), manual.cells());
+
+    let table = Table::new(vec![derived]).with_header(vec!["ID", "NAME"]);
+    let expected = Table::new(vec![manual]).with_header(vec!["ID", "NAME"]);
+    assert_eq!(table.to_string(), expected.to_string());
+}
+
+#[test]
+fn derived_with_alignment() {
+    let row = Aligned { id: 7, name: "Saw" };
+    let (width, last) = cell_widths(30, 2);
+    assert_eq!(
+        row.to_row(30),
+        format!("│ {:>width$} │ {:<last$} │", 7, "Saw")
+    );
+}
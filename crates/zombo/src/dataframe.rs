@@ -0,0 +1,67 @@
+//! Conversion of the items into [`polars::frame::DataFrame`],
+//! enabled by `polars` feature.
+use crate::model::Item;
+use crate::Zomboid;
+use polars::prelude::{Column, DataFrame};
+
+impl<T, E> Zomboid<T>
+where
+    T: Iterator<Item = Result<Item, E>>,
+    E: std::error::Error,
+{
+    /// Consumes iterator of items into a [`DataFrame`] with `id`, `name`,
+    /// `type`, `condition` and `amount` columns.
+    ///
+    /// Amount of items to take and skip can be managed
+    /// by [`Zomboid::set_take`] and [`Zomboid::set_skip`].
+    pub fn to_dataframe(&mut self) -> Result<DataFrame, E> {
+        let mut id = Vec::new();
+        let mut name = Vec::new();
+        let mut item_type = Vec::new();
+        let mut condition = Vec::new();
+        let mut amount = Vec::new();
+
+        for v in self.window() {
+            let item = v?;
+            id.push(item.id);
+            name.push(item.name);
+            item_type.push(item.item_type);
+            condition.push(item.condition.to_string());
+            amount.push(item.amount);
+        }
+
+        Ok(DataFrame::new_infer_height(vec![
+            Column::new("id".into(), id),
+            Column::new("name".into(), name),
+            Column::new("type".into(), item_type),
+            Column::new("condition".into(), condition),
+            Column::new("amount".into(), amount),
+        ])
+        .expect("columns have the same length"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Zomboid;
+    use std::io;
+
+    #[test]
+    fn to_dataframe() {
+        let data = "id,name,type,condition,amount\n\
+                    1,Hummer,Tool,Mint,10\n\
+                    2,Nails,Fasteners,Good,400\n\
+                    3,Garden saw,Tool,New,2\n";
+        let mut r = csv::Reader::from_reader(data.as_bytes());
+        let items = r.deserialize().map(|v| v.map_err(io::Error::other));
+
+        let mut z = Zomboid::new(items);
+        z.set_skip(Some(1));
+        let df = z.to_dataframe().unwrap();
+
+        assert_eq!(df.shape(), (2, 5));
+        let amount = df.column("amount").unwrap().u32().unwrap();
+        let total: u32 = (0..amount.len()).filter_map(|i| amount.get(i)).sum();
+        assert_eq!(total, 402);
+    }
+}
@@ -0,0 +1,67 @@
+use std::fmt::Display;
+
+/// Error of reading the source data that points to the row which failed.
+///
+/// It can be created from [`csv::Error`], so a CSV iterator may be adapted with
+/// `r.deserialize().map(|v| v.map_err(zombo::Error::from))`.
+#[derive(Debug)]
+pub struct Error {
+    row: Option<u64>,
+    message: String,
+}
+
+impl Error {
+    /// Number of the failed data row, starting from 1. Header isn't counted.
+    pub fn row(&self) -> Option<u64> {
+        self.row
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.row {
+            Some(row) => write!(f, "row {}: {}", row, self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<csv::Error> for Error {
+    fn from(e: csv::Error) -> Self {
+        let row = e.position().map(|v| v.record());
+        let message = match e.kind() {
+            csv::ErrorKind::Deserialize { err, .. } => match err.kind() {
+                csv::DeserializeErrorKind::Message(msg) => msg.clone(),
+                _ => err.to_string(),
+            },
+            _ => e.to_string(),
+        };
+        Self { row, message }
+    }
+}
+
+#[cfg(feature = "json")]
+impl From<serde_json::Error> for Error {
+    fn from(e: serde_json::Error) -> Self {
+        Self {
+            row: None,
+            message: e.to_string(),
+        }
+    }
+}
+
+#[cfg(feature = "toml")]
+impl From<::toml::de::Error> for Error {
+    fn from(e: ::toml::de::Error) -> Self {
+        Self {
+            row: None,
+            message: e.to_string(),
+        }
+    }
+}
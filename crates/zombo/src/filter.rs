@@ -0,0 +1,73 @@
+//! Filter module provides [`FilterBuilder`] that combines several conditions
+//! on [`Item`] into a single predicate.
+//!
+//! The predicate can be passed to [`crate::Zomboid::stream_filtered`].
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use zombo::filter::FilterBuilder;
+//! use zombo::Zomboid;
+//!
+//! let mut r = csv::Reader::from_path("path/to/data.csv").unwrap();
+//! let mut z = Zomboid::new(r.deserialize());
+//!
+//! let filter = FilterBuilder::new().condition("Mint").min_amount(50).build();
+//! let table = z.stream_filtered(filter).unwrap();
+//! ```
+use crate::model::Item;
+
+type Predicate = Box<dyn Fn(&Item) -> bool>;
+
+/// Accumulates predicates over [`Item`]. An item passes the built
+/// filter only if it matches all of them.
+#[derive(Default)]
+pub struct FilterBuilder {
+    predicates: Vec<Predicate>,
+}
+
+impl FilterBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keep items of the given condition, compared case-insensitively.
+    pub fn condition(self, condition: impl Into<String>) -> Self {
+        let condition = condition.into();
+        self.predicate(move |v| v.condition.as_str().eq_ignore_ascii_case(&condition))
+    }
+
+    /// Keep items of the given type, compared case-insensitively.
+    pub fn item_type(self, item_type: impl Into<String>) -> Self {
+        let item_type = item_type.into();
+        self.predicate(move |v| v.item_type.eq_ignore_ascii_case(&item_type))
+    }
+
+    /// Keep items with amount greater than or equal to `amount`.
+    pub fn min_amount(self, amount: u32) -> Self {
+        self.predicate(move |v| v.amount >= amount)
+    }
+
+    /// Keep items with amount less than or equal to `amount`.
+    pub fn max_amount(self, amount: u32) -> Self {
+        self.predicate(move |v| v.amount <= amount)
+    }
+
+    /// Keep items whose name contains `query`, compared case-insensitively.
+    pub fn name_contains(self, query: impl Into<String>) -> Self {
+        let query = query.into().to_lowercase();
+        self.predicate(move |v| v.name.to_lowercase().contains(&query))
+    }
+
+    /// Keep items matching an arbitrary predicate.
+    pub fn predicate(mut self, f: impl Fn(&Item) -> bool + 'static) -> Self {
+        self.predicates.push(Box::new(f));
+        self
+    }
+
+    /// Combines the accumulated predicates into a single one.
+    /// Filter without predicates keeps all the items.
+    pub fn build(self) -> impl Fn(&Item) -> bool {
+        move |v| self.predicates.iter().all(|p| p(v))
+    }
+}
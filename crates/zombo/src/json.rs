@@ -0,0 +1,56 @@
+//! Reading items from newline-delimited JSON, enabled by `json` feature.
+//!
+//! Each line of the file is a JSON object with the same fields as the
+//! columns of CSV data. Blank lines are skipped.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use zombo::Zomboid;
+//!
+//! let mut z = Zomboid::new(zombo::json::reader("path/to/data.ndjson").unwrap());
+//! let table = z.stream().unwrap();
+//! ```
+use crate::model::Item;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+
+/// Opens `.ndjson` file at `path` and returns an iterator deserializing
+/// an [`Item`] from each line.
+pub fn reader(
+    path: impl AsRef<Path>,
+) -> io::Result<impl Iterator<Item = Result<Item, serde_json::Error>>> {
+    let f = File::open(path)?;
+    Ok(from_reader(BufReader::new(f)))
+}
+
+/// Same as [`reader`] but reads the lines from `r`.
+pub fn from_reader<R: BufRead>(r: R) -> impl Iterator<Item = Result<Item, serde_json::Error>> {
+    r.lines()
+        .filter(|line| !matches!(line, Ok(v) if v.trim().is_empty()))
+        .map(|line| serde_json::from_str(&line.map_err(serde_json::Error::io)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Zomboid;
+
+    #[test]
+    fn from_reader() {
+        let data = "{\"id\": 1, \"name\": \"Hummer\", \"type\": \"Tool\", \"condition\": \"Mint\", \"amount\": 10}\n\
+                    \n\
+                    {\"id\": 2, \"name\": \"Nails\", \"type\": \"Fasteners\", \"condition\": \"Good\", \"amount\": 400, \"shelf\": \"B2\"}\n";
+        let mut z = Zomboid::new(super::from_reader(data.as_bytes()));
+        let table = z.stream().unwrap();
+        let items = table.as_data();
+
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].name, "Hummer");
+        assert_eq!(items[1].amount, 400);
+        assert_eq!(items[1].extra["shelf"], "B2");
+
+        let mut it = super::from_reader("{\"id\": 1}\n".as_bytes());
+        assert!(it.next().unwrap().is_err());
+    }
+}
@@ -33,7 +33,7 @@
 //! ```
 //!
 //! Default implementation of [`Zomboid::stream`] fully consumes the iterator.
-//! We can use [`Zomboid::set_take`] and [`Zomboid::set_skip`] to create a paginated
+//! We can use [`Zomboid::take`] and [`Zomboid::skip`] to create a paginated
 //! behaviour of the `stream`.
 //!
 //! ```no_run
@@ -44,9 +44,7 @@
 //!
 //! fn read_page() -> Result<Table<Item>, Box<dyn Error>> {
 //!     let mut r = csv::Reader::from_path("path/to/data.csv")?;
-//!     let mut z = Zomboid::new(r.deserialize());
-//!     z.set_take(Some(10));
-//!     z.set_skip(Some(5));
+//!     let mut z = Zomboid::new(r.deserialize()).skip(Some(5)).take(Some(10));
 //!
 //!     Ok(z.stream()?)
 //! }
@@ -54,21 +52,526 @@
 //! ```
 //!
 //! The code above will make `z` object to take next 10 items skipping the first 5 ones.
-use model::{Item, Stat};
-use std::collections::HashMap;
+//! Use [`Zomboid::set_take`] and [`Zomboid::set_skip`] to change them for the next page.
+use chrono::NaiveDate;
+pub use error::Error;
+use merge::MergeSorted;
+use model::{Annotated, Condition, Item, Metric, ParetoRow, PivotRow, RowWithTotal, RunRow, Stat};
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet};
+use std::hash::Hash;
+use std::io::{Read, Write};
+use std::path::Path;
+use std::sync::mpsc::Sender;
+use std::thread;
+use std::time::{Duration, Instant};
 use table::Table;
+use unicode_width::UnicodeWidthStr;
 
+#[cfg(feature = "polars")]
+mod dataframe;
+mod error;
+pub mod filter;
+#[cfg(feature = "json")]
+pub mod json;
+pub mod merge;
 pub mod model;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
 pub mod table;
+#[cfg(feature = "toml")]
+pub mod toml;
+
+const ITEM_HEADER: [&str; 5] = ["ID", "NAME", "TYPE", "CONDITION", "AMOUNT"];
+
+/// Opens `.csv` file in flexible mode, which tolerates rows with inconsistent
+/// amount of fields. Missing trailing fields are filled in, amount with `0`
+/// and the rest ones with an empty value, and redundant ones are ignored.
+///
+/// ```no_run
+/// use zombo::Zomboid;
+///
+/// let mut z = Zomboid::new(zombo::csv_flexible("path/to/data.csv").unwrap());
+/// let table = z.stream().unwrap();
+/// ```
+pub fn csv_flexible(
+    path: impl AsRef<Path>,
+) -> csv::Result<Box<dyn Iterator<Item = Result<Item, csv::Error>>>> {
+    let mut r = csv::ReaderBuilder::new().flexible(true).from_path(path)?;
+    let headers = r.headers()?.clone();
+
+    Ok(Box::new(r.into_records().map(move |rec| {
+        let mut rec = rec?;
+        // Deserializer fails on a row shorter than header, so
+        // the row is padded to the header length.
+        for name in headers.iter().skip(rec.len()) {
+            rec.push_field(if name == "amount" { "0" } else { "" });
+        }
+        rec.truncate(headers.len());
+        rec.deserialize(Some(&headers))
+    })))
+}
+
+/// Opens file at `path` choosing the parser by its extension. Files with
+/// `.json` and `.ndjson` extensions are read as newline-delimited JSON,
+/// see [`json::reader`], and the rest ones as CSV.
+///
+/// Files with `.gz` extension, e.g. `data.csv.gz`, are decompressed and
+/// the parser is chosen by the extension before it.
+///
+/// JSON files can be read only with `json` feature enabled and gzip
+/// compressed ones with `gzip` feature.
+///
+/// ```no_run
+/// use zombo::Zomboid;
+///
+/// let mut z = Zomboid::new(zombo::read_file("path/to/data.ndjson").unwrap());
+/// let table = z.stream().unwrap();
+/// ```
+pub fn read_file(
+    path: impl AsRef<Path>,
+) -> std::io::Result<Box<dyn Iterator<Item = Result<Item, Error>>>> {
+    let (ext, r) = open_decoded(path.as_ref())?;
+    match ext.as_str() {
+        #[cfg(feature = "json")]
+        "json" | "ndjson" => Ok(Box::new(
+            json::from_reader(std::io::BufReader::new(r)).map(|v| v.map_err(Error::from)),
+        )),
+        #[cfg(not(feature = "json"))]
+        "json" | "ndjson" => Err(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            "reading JSON requires `json` feature",
+        )),
+        _ => {
+            let mut r = csv::Reader::from_reader(r);
+            // Deserializing iterator swallows failure to read the header
+            if let Err(e) = r.headers() {
+                return Ok(Box::new(std::iter::once(Err(Error::from(e)))));
+            }
+            Ok(Box::new(
+                r.into_deserialize().map(|v| v.map_err(Error::from)),
+            ))
+        }
+    }
+}
+
+/// Reads the header of the CSV file at `path`, e.g. to check its columns
+/// before streaming. Files are opened the same way as by [`read_file`].
+///
+/// Returns `None` for JSON files since they don't have a header.
+pub fn read_header(path: impl AsRef<Path>) -> Result<Option<Vec<String>>, Error> {
+    let (ext, r) = open_decoded(path.as_ref()).map_err(csv::Error::from)?;
+    match ext.as_str() {
+        "json" | "ndjson" => Ok(None),
+        _ => {
+            let mut r = csv::Reader::from_reader(r);
+            Ok(Some(r.headers()?.iter().map(String::from).collect()))
+        }
+    }
+}
+
+/// Opens file at `path` decompressing it if it has `.gz` extension.
+///
+/// Returns the lowercase extension that the parser is chosen by,
+/// i.e. the one before `.gz` for compressed files.
+fn open_decoded(path: &Path) -> std::io::Result<(String, Box<dyn Read>)> {
+    let extension = |path: &Path| {
+        let ext = path.extension().and_then(|v| v.to_str()).unwrap_or("");
+        ext.to_lowercase()
+    };
+
+    let ext = extension(path);
+    let file = std::fs::File::open(path)?;
+    Ok(match ext.as_str() {
+        #[cfg(feature = "gzip")]
+        "gz" => (
+            extension(&path.with_extension("")),
+            Box::new(flate2::read::GzDecoder::new(file)),
+        ),
+        #[cfg(not(feature = "gzip"))]
+        "gz" => {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::Unsupported,
+                "reading gzip requires `gzip` feature",
+            ))
+        }
+        _ => (ext, Box::new(file)),
+    })
+}
+
+/// Summarizes each of the named `readers` into its name, amount of rows
+/// and a hash of them, e.g. to verify integrity of archived files.
+///
+/// The hash is 64-bit FNV-1a over [`Item::row_hash`] of the rows in order,
+/// so it's stable across runs and platforms.
+///
+/// ```no_run
+/// let files = ["shelter1.csv", "shelter2.csv"];
+/// let readers = files.map(|v| (v.to_string(), zombo::read_file(v).unwrap()));
+/// for (name, rows, hash) in zombo::manifest(readers).unwrap() {
+///     println!("{name} {rows} {hash}");
+/// }
+/// ```
+pub fn manifest<I, E>(
+    readers: impl IntoIterator<Item = (String, I)>,
+) -> Result<Vec<(String, usize, String)>, E>
+where
+    I: Iterator<Item = Result<Item, E>>,
+{
+    readers
+        .into_iter()
+        .map(|(name, items)| {
+            let mut rows = 0;
+            let mut hash = model::FNV_OFFSET;
+            for v in items {
+                hash = model::fnv1a(hash, v?.row_hash().as_bytes());
+                rows += 1;
+            }
+            Ok((name, rows, format!("{:016x}", hash)))
+        })
+        .collect()
+}
+
+/// Compares two outputs of [`Zomboid::describe`], e.g. of yesterday's and
+/// today's data, returning change of share of each group in percentage
+/// points. A group that grew from 20% to 30% has value of `0.1`.
+///
+/// Groups missing in one of the tables have a share of zero there. Rows are
+/// in the order of `new` followed by the groups present only in `old`.
+pub fn stat_delta(old: &Table<Stat>, new: &Table<Stat>) -> Table<Stat> {
+    let share = |table: &Table<Stat>, name: &str| {
+        table
+            .as_data()
+            .iter()
+            .find(|v| v.name == name)
+            .map_or(0.0, |v| v.value)
+    };
+
+    let mut names: Vec<&String> = new.as_data().iter().map(|v| &v.name).collect();
+    for v in old.as_data() {
+        if !names.contains(&&v.name) {
+            names.push(&v.name);
+        }
+    }
+    let rows = names
+        .into_iter()
+        .map(|name| Stat {
+            name: name.clone(),
+            value: share(new, name) - share(old, name),
+        })
+        .collect();
+
+    let label = new
+        .header()
+        .and_then(|v| v.first())
+        .map_or("CONDITION", String::as_str);
+    Table::new(rows).with_header([label, "Δ %"])
+}
+
+/// Deserializes items of `r` coercing fractional amounts, e.g. `10.5`, to
+/// integers according to `mode`. With [`AmountMode::Strict`] it behaves
+/// the same as [`csv::Reader::deserialize`].
+///
+/// ```no_run
+/// use zombo::{AmountMode, Zomboid};
+///
+/// let r = csv::Reader::from_path("path/to/data.csv").unwrap();
+/// let mut z = Zomboid::new(zombo::csv_float_amounts(r, AmountMode::Round).unwrap());
+/// let table = z.stream().unwrap();
+/// ```
+pub fn csv_float_amounts<R: Read + 'static>(
+    mut r: csv::Reader<R>,
+    mode: AmountMode,
+) -> csv::Result<Box<dyn Iterator<Item = Result<Item, csv::Error>>>> {
+    let headers = r.headers()?.clone();
+    let column = headers.iter().position(|v| v == "amount");
+
+    Ok(Box::new(r.into_records().map(move |rec| {
+        let rec = rec?;
+        match column.and_then(|i| Some((i, rec.get(i)?))) {
+            Some((i, amount)) => {
+                let amount = mode.coerce(amount);
+                let rec: csv::StringRecord = rec
+                    .iter()
+                    .enumerate()
+                    .map(|(j, v)| if i == j { amount.as_str() } else { v })
+                    .collect();
+                rec.deserialize(Some(&headers))
+            }
+            None => rec.deserialize(Some(&headers)),
+        }
+    })))
+}
+
+/// Wraps `r` into a reader that fails once more than `max_bytes` are read.
+///
+/// It protects from running out of memory on adversarial inputs and can be
+/// used with [`csv::Reader::from_reader`].
+///
+/// ```no_run
+/// use std::fs::File;
+/// use zombo::Zomboid;
+///
+/// let f = File::open("path/to/data.csv").unwrap();
+/// let mut r = csv::Reader::from_reader(zombo::limited_reader(f, 1024 * 1024));
+/// let mut z = Zomboid::new(r.deserialize());
+/// ```
+pub fn limited_reader<R: Read>(r: R, max_bytes: u64) -> LimitedReader<R> {
+    LimitedReader {
+        inner: r,
+        max_bytes,
+        remaining: max_bytes,
+    }
+}
+
+/// Reader created by [`limited_reader`].
+#[derive(Debug)]
+pub struct LimitedReader<R> {
+    inner: R,
+    max_bytes: u64,
+    remaining: u64,
+}
+
+impl<R: Read> Read for LimitedReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        if self.remaining == 0 {
+            // Limit is reached, check if there is anything beyond it
+            return match self.inner.read(&mut [0u8])? {
+                0 => Ok(0),
+                _ => Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("input exceeds the limit of {} bytes", self.max_bytes),
+                )),
+            };
+        }
+
+        let len = usize::try_from(self.remaining).map_or(buf.len(), |v| v.min(buf.len()));
+        let n = self.inner.read(&mut buf[..len])?;
+        self.remaining -= n as u64;
+        Ok(n)
+    }
+}
+
+/// Streaming options of [`Zomboid`] gathered in one place.
+///
+/// Each field mirrors a setter of [`Zomboid`], e.g. `take` is the same
+/// as [`Zomboid::set_take`]. The config is serializable, so it may be
+/// stored and reused as a saved view.
+#[derive(Debug, Default, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct ZomboidConfig {
+    pub take: Option<usize>,
+    pub skip: Option<usize>,
+    pub max_rows: Option<usize>,
+    pub lenient: bool,
+    /// Column to sort by and whether the order is ascending,
+    /// see [`Zomboid::sort_by`].
+    pub sort: Option<(SortKey, bool)>,
+}
+
+/// What the percentages of [`Zomboid::describe_with`] are calculated on.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Basis {
+    /// Sum of amounts of the items
+    Amount,
+    /// Number of rows, regardless of amount
+    Count,
+    /// Sum of amounts multiplied by weight of the item condition.
+    /// Conditions missing in the map have weight of `1.0`.
+    Weighted(HashMap<String, f64>),
+}
+
+/// Column of the items to sort by, see [`Zomboid::sort_by`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum SortKey {
+    Id,
+    Name,
+    Type,
+    Condition,
+    Amount,
+}
+
+impl SortKey {
+    /// Compares `a` and `b` by the column in ascending order.
+    fn compare(&self, a: &Item, b: &Item) -> Ordering {
+        match self {
+            Self::Id => a.id.cmp(&b.id),
+            Self::Name => a.name.cmp(&b.name),
+            Self::Type => a.item_type.cmp(&b.item_type),
+            Self::Condition => a.condition.cmp(&b.condition),
+            Self::Amount => a.amount.cmp(&b.amount),
+        }
+    }
+}
+
+/// How [`csv_float_amounts`] treats amounts given as fractional numbers.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum AmountMode {
+    /// Only integer amounts are accepted
+    #[default]
+    Strict,
+    /// Rounds to the nearest integer, halves away from zero, e.g. `10.5` is `11`
+    Round,
+    /// Drops the fraction, e.g. `10.9` is `10`
+    Truncate,
+}
+
+impl AmountMode {
+    /// Converts a raw `amount` into an integer one. Values that aren't
+    /// finite non-negative numbers are returned as is, so that they fail
+    /// deserialization with a descriptive error.
+    fn coerce(self, amount: &str) -> String {
+        let v = match (self, amount.trim().parse::<f64>()) {
+            (Self::Strict, _) | (_, Err(_)) => return amount.to_string(),
+            (_, Ok(v)) if !v.is_finite() || v < 0.0 => return amount.to_string(),
+            (Self::Round, Ok(v)) => v.round(),
+            (Self::Truncate, Ok(v)) => v.trunc(),
+        };
+        format!("{v:.0}")
+    }
+}
+
+/// Field of the items that [`Zomboid::describe_by`] groups them by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GroupField {
+    Condition,
+    Type,
+}
+
+impl GroupField {
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Condition => "CONDITION",
+            Self::Type => "TYPE",
+        }
+    }
+}
+
+/// Several summaries of the data computed by [`Zomboid::report`].
+#[derive(Debug)]
+pub struct Report {
+    /// Percentage of amount per condition, same as [`Zomboid::describe`]
+    pub conditions: Table<Stat>,
+    /// Total amount per item type
+    pub types: Table<Metric>,
+    /// Min, max, mean and median amount of a row.
+    /// All of them are zero if there is no data.
+    pub amounts: Table<Metric>,
+}
+
+/// Amount statistics of the items computed by [`Zomboid::summary`].
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Summary {
+    /// Sum of amounts of all the items
+    pub total: u64,
+    /// Mean amount per item, `0.0` if there are no items
+    pub mean: f64,
+    /// Smallest amount, `None` if there are no items
+    pub min: Option<u32>,
+    /// Largest amount, `None` if there are no items
+    pub max: Option<u32>,
+}
+
+/// Accumulator of the percentages computed by [`Zomboid::describe`] that
+/// items are pushed into one by one, e.g. from a loop of the caller.
+///
+/// ```
+/// use zombo::Describer;
+///
+/// let data = "id,name,type,condition,amount\n1,Saw,Tool,Mint,2\n";
+/// let mut r = csv::Reader::from_reader(data.as_bytes());
+/// let mut describer = Describer::new();
+/// for item in r.deserialize() {
+///     describer.push(&item.unwrap());
+/// }
+/// let table = describer.finish();
+/// ```
+#[derive(Debug)]
+pub struct Describer {
+    field: GroupField,
+    basis: Basis,
+    per_group: HashMap<String, f64>,
+    total: f64,
+}
+
+impl Describer {
+    /// Creates accumulator of percentage of amount per condition.
+    pub fn new() -> Self {
+        Self::grouped(GroupField::Condition, Basis::Amount)
+    }
+
+    /// Creates accumulator of percentages per value of `field`
+    /// calculated on the given `basis`.
+    pub fn grouped(field: GroupField, basis: Basis) -> Self {
+        Self {
+            field,
+            basis,
+            per_group: HashMap::new(),
+            total: 0.0,
+        }
+    }
+
+    pub fn push(&mut self, item: &Item) {
+        let value = match &self.basis {
+            Basis::Amount => item.amount as f64,
+            Basis::Count => 1.0,
+            Basis::Weighted(weights) => {
+                item.amount as f64 * weights.get(item.condition.as_str()).copied().unwrap_or(1.0)
+            }
+        };
+        let group = match self.field {
+            GroupField::Condition => item.condition.to_string(),
+            GroupField::Type => item.item_type.clone(),
+        };
+        *self.per_group.entry(group).or_insert(0.0) += value;
+        self.total += value;
+    }
+
+    /// Builds the table of percentages of the pushed items.
+    pub fn finish(self) -> Table<Stat> {
+        percentage_table(self.field.label(), self.per_group, self.total)
+    }
+}
+
+impl Default for Describer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 #[derive(Debug)]
 pub struct Zomboid<T> {
     it: T,
     _take: Option<usize>,
     _skip: Option<usize>,
+    _below: Option<u32>,
+    _lenient: bool,
+    _condition: Option<String>,
+    _search: Option<String>,
+    _sort: Option<(SortKey, bool)>,
+    _merge_by_id: bool,
+    _max_rows: Option<usize>,
 }
 
 impl<T> Zomboid<T> {
+    /// Same as [`Zomboid::set_take`] but in a builder style,
+    /// e.g. `Zomboid::new(it).skip(Some(5)).take(Some(10))`.
+    pub fn take(mut self, v: Option<usize>) -> Self {
+        self.set_take(v);
+        self
+    }
+
+    /// Same as [`Zomboid::set_skip`] but in a builder style,
+    /// e.g. `Zomboid::new(it).skip(Some(5)).take(Some(10))`.
+    pub fn skip(mut self, v: Option<usize>) -> Self {
+        self.set_skip(v);
+        self
+    }
+
     /// Set how many items to take on next [`Zomboid::stream`] call
     pub fn set_take(&mut self, v: Option<usize>) {
         self._take = v;
@@ -78,6 +581,145 @@ impl<T> Zomboid<T> {
     pub fn set_skip(&mut self, v: Option<usize>) {
         self._skip = v;
     }
+
+    /// Keep only items with amount below `v` on next [`Zomboid::stream`] call.
+    ///
+    /// The filter is applied before skip and take, so pagination
+    /// works over the filtered items.
+    pub fn set_below(&mut self, v: Option<u32>) {
+        self._below = v;
+    }
+
+    /// Silently drop items that failed to be read instead of
+    /// returning the error on next [`Zomboid::stream`] call.
+    pub fn set_lenient(&mut self, v: bool) {
+        self._lenient = v;
+    }
+
+    /// Keep only items of the given condition, compared case-insensitively,
+    /// on next [`Zomboid::stream`] call. `None` clears the filter.
+    ///
+    /// The filter is applied before skip and take, so pagination
+    /// works over the filtered items.
+    pub fn filter_condition(&mut self, condition: Option<&str>) {
+        self._condition = condition.map(String::from);
+    }
+
+    /// Keep only items whose name contains `query`, compared case-insensitively,
+    /// on next [`Zomboid::stream`] call. `None` clears the search.
+    ///
+    /// The search is applied before skip and take along with the other filters,
+    /// e.g. [`Zomboid::filter_condition`], so items have to match all of them.
+    pub fn search(&mut self, query: Option<String>) {
+        self._search = query.map(|v| v.to_lowercase());
+    }
+
+    /// Sort items of next [`Zomboid::stream`] call by `key`. The sort is
+    /// stable, so items with equal keys keep their input order.
+    ///
+    /// Sorting is done over the items within skip and take window,
+    /// so the whole window is buffered before the table is built.
+    pub fn sort_by(&mut self, key: SortKey, ascending: bool) {
+        self._sort = Some((key, ascending));
+    }
+
+    /// Limit amount of rows of the table built by next [`Zomboid::stream`] call.
+    ///
+    /// Unlike [`Zomboid::set_take`], the limit is applied after merging
+    /// and sorting, e.g. to keep the first rows of the sorted table.
+    pub fn set_max_rows(&mut self, v: Option<usize>) {
+        self._max_rows = v;
+    }
+
+    /// Merge items sharing the same id on next [`Zomboid::stream`] call.
+    ///
+    /// Merged row has the summed amount, name and type of the first item
+    /// of the id and its distinct conditions joined with a comma in order
+    /// of their severity. Rows are ordered by the first occurrence of id.
+    /// The sum saturates at [`u32::MAX`].
+    ///
+    /// Skip and take are applied to the items before merging, while sorting,
+    /// see [`Zomboid::sort_by`], is applied to the merged rows.
+    pub fn merge_by_id(&mut self, merge: bool) {
+        self._merge_by_id = merge;
+    }
+
+    /// Restores all the streaming options, e.g. take, skip, filters, search,
+    /// sorting and merging, to their defaults, so that [`Zomboid`] can be
+    /// reused for another query.
+    ///
+    /// It doesn't rewind the iterator, next [`Zomboid::stream`] call continues
+    /// from the item where the previous one stopped.
+    pub fn reset(&mut self) {
+        self._take = None;
+        self._skip = None;
+        self._below = None;
+        self._lenient = false;
+        self._condition = None;
+        self._search = None;
+        self._sort = None;
+        self._merge_by_id = false;
+        self._max_rows = None;
+    }
+
+    /// Merges and sorts `items` and limits amount of them as configured,
+    /// see [`Zomboid::merge_by_id`], [`Zomboid::sort_by`] and [`Zomboid::set_max_rows`].
+    fn arrange(&self, items: Vec<Item>) -> Vec<Item> {
+        let mut items = self.merge_items(items);
+        self.sort_items(&mut items);
+        items.truncate(self._max_rows.unwrap_or(usize::MAX));
+        items
+    }
+
+    /// Merges `items` by id if it's set by [`Zomboid::merge_by_id`].
+    fn merge_items(&self, items: Vec<Item>) -> Vec<Item> {
+        if !self._merge_by_id {
+            return items;
+        }
+
+        let mut merged = Vec::<(Item, BTreeSet<Condition>)>::new();
+        let mut index = HashMap::<u32, usize>::new();
+        for item in items {
+            match index.get(&item.id) {
+                Some(&i) => {
+                    let (row, conditions) = &mut merged[i];
+                    row.amount = row.amount.saturating_add(item.amount);
+                    conditions.insert(item.condition);
+                }
+                None => {
+                    index.insert(item.id, merged.len());
+                    let conditions = BTreeSet::from([item.condition.clone()]);
+                    merged.push((item, conditions));
+                }
+            }
+        }
+
+        merged
+            .into_iter()
+            .map(|(mut row, conditions)| {
+                if conditions.len() > 1 {
+                    // Joined conditions are kept as `Condition::Other`
+                    let joined: Vec<&str> = conditions.iter().map(Condition::as_str).collect();
+                    row.condition = Condition::Other(joined.join(","));
+                }
+                row
+            })
+            .collect()
+    }
+
+    /// Sorts `items` as set by [`Zomboid::sort_by`] if any.
+    fn sort_items(&self, items: &mut [Item]) {
+        let Some((key, ascending)) = self._sort else {
+            return;
+        };
+        items.sort_by(|a, b| {
+            let ord = key.compare(a, b);
+            match ascending {
+                true => ord,
+                false => ord.reverse(),
+            }
+        });
+    }
 }
 
 impl<T, E> Zomboid<T>
@@ -86,14 +728,27 @@ where
     E: std::error::Error,
 {
     pub fn new(it: T) -> Self {
+        Self::with_config(it, ZomboidConfig::default())
+    }
+
+    /// Creates [`Zomboid`] with streaming options taken from `config`.
+    pub fn with_config(it: T, config: ZomboidConfig) -> Self {
         Self {
             it,
-            _take: None,
-            _skip: None,
+            _take: config.take,
+            _skip: config.skip,
+            _below: None,
+            _lenient: config.lenient,
+            _condition: None,
+            _search: None,
+            _sort: config.sort,
+            _merge_by_id: false,
+            _max_rows: config.max_rows,
         }
     }
 
-    /// Consumes iterator of Items and builds a table.
+    /// Consumes iterator of Items and builds a table with a footer showing
+    /// the total amount of the items, see [`Table::with_auto_footer`].
     ///
     /// Amount of items to take and skip can be managed
     /// by [`Zomboid::set_take`] and [`Zomboid::set_skip`].
@@ -102,130 +757,2058 @@ where
     ///
     /// Method returns [`Result<T, E>`] where `T` is [`table::Table<Item>`].
     pub fn stream(&mut self) -> Result<Table<Item>, E> {
-        let items: Result<Vec<Item>, E> = self
-            .it
-            .by_ref()
-            .skip(self._skip.unwrap_or(0))
-            .take(self._take.unwrap_or(usize::MAX))
-            .collect();
-        Ok(Table::new(items?).with_header(vec!["ID", "NAME", "TYPE", "CONDITION", "AMOUNT"]))
+        let items = self.window().collect::<Result<Vec<Item>, E>>()?;
+        let items = self.arrange(items);
+        Ok(Table::new(items)
+            .with_header(ITEM_HEADER.to_vec())
+            .with_auto_footer())
     }
 
-    /// Consumes iterator of items and calculate basic statistics
-    /// over the processed data.
+    /// Same as [`Zomboid::stream`] but keeps only items matching `predicate`.
     ///
-    /// Currently, it calculates only a percentage of items of each
-    /// condition.
+    /// The predicate is applied before skip and take, so pagination works over
+    /// the matched items. Use [`filter::FilterBuilder`] to combine several conditions.
+    pub fn stream_filtered(&mut self, predicate: impl Fn(&Item) -> bool) -> Result<Table<Item>, E> {
+        let items = self
+            .window_filtered(predicate)
+            .collect::<Result<Vec<Item>, E>>()?;
+        let items = self.arrange(items);
+        Ok(Table::new(items).with_header(ITEM_HEADER.to_vec()))
+    }
+
+    /// Same as [`Zomboid::stream`] but sorts the items by several `keys`.
+    /// Items equal by the first key are sorted by the second one and so on.
     ///
-    /// Amount of items to take and skip can be managed
-    /// by [`Zomboid::set_take`] and [`Zomboid::set_skip`].
-    pub fn describe(&mut self) -> Result<Table<Stat>, E> {
-        let mut map_per_condition = HashMap::<String, u32>::new();
-        let mut total = 0u32;
-
-        for v in &mut self
-            .it
-            .by_ref()
-            .skip(self._skip.unwrap_or(0))
-            .take(self._take.unwrap_or(usize::MAX))
-        {
-            let item = v?;
-            *map_per_condition.entry(item.condition).or_insert(0) += item.amount;
-            total += item.amount;
-        }
+    /// Key at index `i` is sorted descending if `descs[i]` is `true`, keys
+    /// without a matching flag are sorted ascending. The sort is stable.
+    ///
+    /// The `keys` replace the sort set by [`Zomboid::sort_by`], while merging
+    /// and the rows limit are applied the same way as by [`Zomboid::stream`].
+    pub fn stream_sorted_multi(
+        &mut self,
+        keys: &[SortKey],
+        descs: &[bool],
+    ) -> Result<Table<Item>, E> {
+        let items = self.window().collect::<Result<Vec<Item>, E>>()?;
+        let mut items = self.merge_items(items);
+        items.sort_by(|a, b| {
+            keys.iter()
+                .enumerate()
+                .map(|(i, key)| match descs.get(i) {
+                    Some(true) => key.compare(a, b).reverse(),
+                    _ => key.compare(a, b),
+                })
+                .find(|ord| ord.is_ne())
+                .unwrap_or(Ordering::Equal)
+        });
+        items.truncate(self._max_rows.unwrap_or(usize::MAX));
+        Ok(Table::new(items)
+            .with_header(ITEM_HEADER.to_vec())
+            .with_auto_footer())
+    }
 
-        let mut stats = Vec::<Stat>::with_capacity(map_per_condition.len());
-        for (name, amount) in map_per_condition.into_iter() {
-            stats.push(Stat {
-                name,
-                value: (amount as f64) / (total as f64),
-            });
+    /// Same as [`Zomboid::stream`] but replaces type of each item found
+    /// in `map` with the mapped one. Unmapped types are kept as is.
+    ///
+    /// Types are remapped before merging and sorting, so sorting by type
+    /// orders the items by the mapped types.
+    pub fn remap_types(&mut self, map: &HashMap<String, String>) -> Result<Table<Item>, E> {
+        let mut items = Vec::new();
+        for v in self.window() {
+            let mut item = v?;
+            if let Some(t) = map.get(&item.item_type) {
+                item.item_type = t.clone();
+            }
+            items.push(item);
         }
+        let items = self.arrange(items);
+        Ok(Table::new(items)
+            .with_header(ITEM_HEADER.to_vec())
+            .with_auto_footer())
+    }
 
-        Ok(Table::new(stats)
-            .with_header(vec!["CONDITION", "%"])
-            .with_width(40))
+    /// Same as [`Zomboid::stream`] but stops pulling items once `deadline`
+    /// has passed and returns the items collected so far.
+    ///
+    /// The deadline is checked between items, so a slow source may
+    /// exceed it by the time of reading a single item. Merging, sorting and
+    /// the rows limit are applied to the collected items.
+    pub fn stream_until(&mut self, deadline: Instant) -> Result<Table<Item>, E> {
+        let mut items = Vec::new();
+        let mut window = self.window();
+        while Instant::now() < deadline {
+            match window.next() {
+                Some(v) => items.push(v?),
+                None => break,
+            }
+        }
+        drop(window);
+        let items = self.arrange(items);
+        Ok(Table::new(items)
+            .with_header(ITEM_HEADER.to_vec())
+            .with_auto_footer())
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use crate::{model::Item, Zomboid};
-    use csv::Writer;
-    use std::error::Error;
-    use std::path::Path;
-    use std::path::PathBuf;
-    use std::{fs, io};
-    use uuid::Uuid;
+    /// Same as [`Zomboid::stream`] but tolerates transient errors of the source.
+    ///
+    /// When `is_transient` returns `true` for an error, the next item is pulled
+    /// again, up to `max_retries` consecutive times. Any other error, or a transient
+    /// one after the retries are exhausted, aborts the stream. Skipped errors
+    /// don't count towards take and skip.
+    ///
+    /// Filters, sorting and merging configured on [`Zomboid`] are applied
+    /// the same way as by [`Zomboid::stream`].
+    pub fn stream_retry(
+        &mut self,
+        max_retries: usize,
+        is_transient: impl Fn(&E) -> bool,
+    ) -> Result<Table<Item>, E> {
+        let lenient = self._lenient;
+        let mut retries = 0;
+        let source = self.it.by_ref().filter(move |v| match v {
+            Ok(_) => {
+                retries = 0;
+                true
+            }
+            Err(_) if lenient => {
+                retries = 0;
+                false
+            }
+            Err(e) if retries < max_retries && is_transient(e) => {
+                retries += 1;
+                false
+            }
+            Err(_) => true,
+        });
+        let keep = item_filter(self._below, self._condition.clone(), self._search.clone());
+        let items = windowed(source, keep, lenient, self._skip, self._take)
+            .collect::<Result<Vec<Item>, E>>()?;
 
-    // TODO: use tempfile crate!
-    const BASE_PATH: &str = "~/.cache/rust/testing";
+        let items = self.arrange(items);
+        Ok(Table::new(items).with_header(ITEM_HEADER.to_vec()))
+    }
 
-    fn setup_csv() -> Result<PathBuf, Box<dyn Error>> {
-        fs::create_dir_all(BASE_PATH)?;
-        let path = format!("{}/{}.csv", BASE_PATH, Uuid::new_v4());
-        let mut writer = Writer::from_path(&path)?;
+    /// Same as [`Zomboid::stream`] but annotates each row with the running
+    /// total of amount. The total starts from zero after the skipped items.
+    pub fn stream_with_running_total(&mut self) -> Result<Table<RowWithTotal>, E> {
+        let mut running = 0u64;
+        let mut rows = Vec::new();
+        for v in self.window() {
+            let item = v?;
+            running += item.amount as u64;
+            rows.push(RowWithTotal { item, running });
+        }
 
-        writer.write_record(["id", "name", "type", "condition", "amount"])?;
-        writer.write_record(["1", "Hummer", "Tool", "Mint", "10"])?;
-        writer.write_record(["2", "Nails", "Fasteners", "Good", "400"])?;
-        writer.write_record(["2", "Nails", "Fasteners", "Mint", "100"])?;
-        writer.write_record(["3", "Garden saw", "Tool", "New", "2"])?;
-        writer.write_record(["4", "Metal saw", "Tool", "New", "2"])?;
+        let mut header = ITEM_HEADER.to_vec();
+        header.push("RUNNING");
+        Ok(Table::new(rows).with_header(header))
+    }
 
-        Ok(path.into())
+    /// Consumes iterator of items and writes them to `w` as tab-separated values
+    /// without buffering the whole page.
+    ///
+    /// The first line is a header with field names as they are in the source
+    /// data, i.e. `id`, `name`, `type`, `condition` and `amount`. Tabs, newlines
+    /// and backslashes inside of the fields are escaped as `\t`, `\n`, `\r` and `\\`.
+    ///
+    /// Amount of items to take and skip can be managed
+    /// by [`Zomboid::set_take`] and [`Zomboid::set_skip`].
+    pub fn stream_tsv<W: Write>(&mut self, mut w: W) -> Result<(), E>
+    where
+        E: From<std::io::Error>,
+    {
+        writeln!(w, "id\tname\ttype\tcondition\tamount")?;
+        for v in self.window() {
+            let item = v?;
+            writeln!(
+                w,
+                "{}\t{}\t{}\t{}\t{}",
+                item.id,
+                escape_tsv(&item.name),
+                escape_tsv(&item.item_type),
+                escape_tsv(item.condition.as_str()),
+                item.amount
+            )?;
+        }
+        Ok(())
     }
 
-    fn teardown_csv(path: impl AsRef<Path>) -> io::Result<()> {
-        fs::remove_file(path)?;
+    /// Same as [`Zomboid::stream`] but writes the rendered table to `w` row
+    /// by row as the items are read, so the page isn't held in memory.
+    ///
+    /// The output is the same as of the formatted [`Zomboid::stream`] table
+    /// followed by a newline. Sorting and merging configured on [`Zomboid`]
+    /// aren't applied since they need all the items at once.
+    ///
+    /// Amount of items to take and skip can be managed
+    /// by [`Zomboid::set_take`] and [`Zomboid::set_skip`].
+    pub fn stream_to<W: Write>(&mut self, mut w: W) -> Result<(), E>
+    where
+        E: From<std::io::Error>,
+    {
+        let layout = Table::<Item>::new(Vec::new()).with_header(ITEM_HEADER.to_vec());
+        w.write_all(layout.render_head().as_bytes())?;
+        let (mut total, mut rows) = (0, 0);
+        for v in self.window() {
+            let item = v?;
+            total += item.amount as u64;
+            rows += 1;
+            w.write_all(layout.render_row(&item).as_bytes())?;
+        }
+        if rows == 0 {
+            w.write_all(layout.render_empty().as_bytes())?;
+        }
+        let layout = layout.with_footer(model::total_footer(total));
+        w.write_all(layout.render_bottom().as_bytes())?;
         Ok(())
     }
 
-    #[ignore = "experiment"]
-    #[test]
-    fn csv_reader() {
-        let file_path = setup_csv().unwrap();
-        let mut r = csv::Reader::from_path(&file_path).unwrap();
+    /// Same as [`Zomboid::stream`] but marks each row whether it passes
+    /// `validate` in an additional `VALID` column. Invalid rows are kept.
+    pub fn stream_annotated(
+        &mut self,
+        validate: impl Fn(&Item) -> bool,
+    ) -> Result<Table<Annotated>, E> {
+        let mut rows = Vec::new();
+        for v in self.window() {
+            let item = v?;
+            let valid = validate(&item);
+            rows.push(Annotated { item, valid });
+        }
 
-        for res in r.deserialize() {
-            let rec: Item = res.unwrap();
-            println!("{:?}", rec);
+        let mut header = ITEM_HEADER.to_vec();
+        header.push("VALID");
+        Ok(Table::new(rows).with_header(header))
+    }
+
+    /// Same as [`Zomboid::stream`] but collapses consecutive identical items
+    /// into a single row annotated with the amount of repeats.
+    pub fn compress_runs(&mut self) -> Result<Table<RunRow>, E> {
+        let mut rows = Vec::<RunRow>::new();
+        for v in self.window() {
+            let item = v?;
+            match rows.last_mut() {
+                Some(run) if run.item == item => run.count += 1,
+                _ => rows.push(RunRow { item, count: 1 }),
+            }
         }
 
-        teardown_csv(file_path).unwrap();
+        let mut header = ITEM_HEADER.to_vec();
+        header.push("COUNT");
+        Ok(Table::new(rows).with_header(header))
     }
 
-    #[test]
-    fn zomboid_csv() {
-        let file_path = setup_csv().unwrap();
-        let mut r = csv::Reader::from_path(&file_path).unwrap();
-        let mut z = Zomboid::new(r.deserialize());
+    /// Same as [`Zomboid::stream`] but keeps only the first item per key
+    /// computed by `key`, e.g. `|v| v.id` or `|v| (v.id, v.condition.clone())`.
+    pub fn dedup_by_key<K, F>(&mut self, key: F) -> Result<Table<Item>, E>
+    where
+        K: Eq + Hash,
+        F: FnMut(&Item) -> K,
+    {
+        self.dedup_report(key).map(|(table, _)| table)
+    }
 
-        z.set_take(Some(2));
-        z.set_skip(Some(2));
+    /// Same as [`Zomboid::dedup_by_key`] keyed by item id, but also returns
+    /// the number of dropped duplicate rows.
+    pub fn dedup_by_id_report(&mut self) -> Result<(Table<Item>, usize), E> {
+        self.dedup_report(|v| v.id)
+    }
 
-        assert_eq!(z._skip, Some(2));
-        assert_eq!(z._take, Some(2));
+    fn dedup_report<K, F>(&mut self, mut key: F) -> Result<(Table<Item>, usize), E>
+    where
+        K: Eq + Hash,
+        F: FnMut(&Item) -> K,
+    {
+        let mut seen = HashSet::<K>::new();
+        let mut items = Vec::new();
+        let mut dropped = 0;
+        for v in self.window() {
+            let item = v?;
+            match seen.insert(key(&item)) {
+                true => items.push(item),
+                false => dropped += 1,
+            }
+        }
+        Ok((Table::new(items).with_header(ITEM_HEADER.to_vec()), dropped))
+    }
 
-        let table = z.stream().unwrap();
-        let data = table.as_data();
+    /// Consumes iterator of items and calculate basic statistics
+    /// over the processed data.
+    ///
+    /// Currently, it calculates only a percentage of items of each
+    /// condition.
+    ///
+    /// Amount of items to take and skip can be managed
+    /// by [`Zomboid::set_take`] and [`Zomboid::set_skip`].
+    pub fn describe(&mut self) -> Result<Table<Stat>, E> {
+        self.describe_grouped(GroupField::Condition, Basis::Amount)
+    }
 
-        assert_eq!(data.len(), 2);
-        assert_eq!(data[0].id, 2);
-        assert_eq!(data[1].id, 3);
+    /// Consumes iterator of items and sums their amounts per condition,
+    /// i.e. the numbers [`Zomboid::describe`] calculates percentages of.
+    /// Sums saturate at [`u32::MAX`].
+    ///
+    /// Amount of items to take and skip can be managed
+    /// by [`Zomboid::set_take`] and [`Zomboid::set_skip`].
+    pub fn condition_counts(&mut self) -> Result<HashMap<String, u32>, E> {
+        let amounts = self.condition_amounts()?;
+        // Float to integer casts saturate
+        Ok(amounts.into_iter().map(|(k, v)| (k, v as u32)).collect())
+    }
 
-        println!("{}", table);
+    /// Sums amounts of the items per value of `key` in floats, the same
+    /// as [`Describer`] does, so that the sums don't overflow.
+    fn amounts_by(&mut self, key: impl Fn(&Item) -> String) -> Result<HashMap<String, f64>, E> {
+        let mut amounts = HashMap::<String, f64>::new();
+        for v in self.window() {
+            let item = v?;
+            *amounts.entry(key(&item)).or_insert(0.0) += item.amount as f64;
+        }
+        Ok(amounts)
+    }
 
-        teardown_csv(file_path).unwrap();
+    /// Sums amounts of the items per condition, i.e. the numbers
+    /// [`Zomboid::describe`] calculates percentages of.
+    fn condition_amounts(&mut self) -> Result<HashMap<String, f64>, E> {
+        self.amounts_by(|v| v.condition.to_string())
     }
 
-    #[test]
-    fn describe() {
-        let file_path = setup_csv().unwrap();
-        let mut r = csv::Reader::from_path(&file_path).unwrap();
-        let mut z = Zomboid::new(r.deserialize());
+    /// Same as [`Zomboid::describe`] but percentages are calculated
+    /// per value of the given `field`, e.g. per item type.
+    pub fn describe_by(&mut self, field: GroupField) -> Result<Table<Stat>, E> {
+        self.describe_grouped(field, Basis::Amount)
+    }
 
-        let table = z.describe().unwrap();
-        println!("{}", table);
+    /// Same as [`Zomboid::describe`] but percentages are calculated
+    /// on the given [`Basis`].
+    pub fn describe_with(&mut self, basis: Basis) -> Result<Table<Stat>, E> {
+        self.describe_grouped(GroupField::Condition, basis)
+    }
+
+    fn describe_grouped(&mut self, field: GroupField, basis: Basis) -> Result<Table<Stat>, E> {
+        let mut describer = Describer::grouped(field, basis);
+        for v in self.window() {
+            describer.push(&v?);
+        }
+        Ok(describer.finish())
+    }
+
+    /// Consumes iterator of items and calculates Shannon entropy of amount
+    /// distribution over conditions in bits, i.e. `-Σ p·log2(p)`.
+    /// It's `0.0` for a single condition or no items.
+    ///
+    /// Amount of items to take and skip can be managed
+    /// by [`Zomboid::set_take`] and [`Zomboid::set_skip`].
+    pub fn condition_entropy(&mut self) -> Result<f64, E> {
+        let per_condition = self.condition_amounts()?;
+        let total: f64 = per_condition.values().sum();
+
+        let entropy = per_condition
+            .values()
+            .filter(|v| **v > 0.0)
+            .map(|v| v / total)
+            .map(|p| -p * p.log2())
+            .sum::<f64>();
+        // Avoid `-0.0` for a single condition
+        Ok(entropy.abs())
+    }
+
+    /// Consumes iterator of items and calculates a percentage of amount
+    /// of items by their age, i.e. days since [`Item::added`] till today.
+    ///
+    /// Items are bucketed into `< 7 days`, `< 30 days` and `older`.
+    /// Items without a date go into `unknown`.
+    ///
+    /// Amount of items to take and skip can be managed
+    /// by [`Zomboid::set_take`] and [`Zomboid::set_skip`].
+    pub fn freshness(&mut self) -> Result<Table<Stat>, E> {
+        self.freshness_at(chrono::Local::now().date_naive())
+    }
+
+    /// Same as [`Zomboid::freshness`] but age is calculated relative
+    /// to the given `today` date.
+    pub fn freshness_at(&mut self, today: NaiveDate) -> Result<Table<Stat>, E> {
+        let per_bucket = self.amounts_by(|item| {
+            let bucket = match item.added.map(|v| (today - v).num_days()) {
+                None => "unknown",
+                Some(..7) => "< 7 days",
+                Some(..30) => "< 30 days",
+                Some(_) => "older",
+            };
+            bucket.to_string()
+        })?;
+        let total = per_bucket.values().sum();
+
+        Ok(percentage_table("AGE", per_bucket, total))
+    }
+
+    /// Same as [`Zomboid::describe`] but conditions are sorted by their share
+    /// descending and each row has the cumulative share of it and all
+    /// the rows above. Ties are ordered by name.
+    pub fn describe_pareto(&mut self) -> Result<Table<ParetoRow>, E> {
+        let mut stats = self.describe()?;
+        stats.sort_by_value(true);
+
+        let mut cumulative = 0.0;
+        let rows = stats
+            .into_data()
+            .into_iter()
+            .map(|v| {
+                cumulative += v.value;
+                ParetoRow {
+                    name: v.name,
+                    value: v.value,
+                    cumulative,
+                }
+            })
+            .collect();
+
+        Ok(Table::new(rows).with_header(vec!["CONDITION", "%", "CUMULATIVE %"]))
+    }
+
+    /// Same as [`Zomboid::describe`] but no condition is shown with more than
+    /// `cap` share, e.g. `0.5` for 50%.
+    ///
+    /// The share cut from the capped conditions is redistributed over the rest
+    /// proportionally to their amounts. This is repeated while redistribution
+    /// pushes other conditions over the cap, so the shares still sum to 100%
+    /// unless the cap is too low for all the conditions to fit into it.
+    pub fn describe_capped(&mut self, cap: f64) -> Result<Table<Stat>, E> {
+        let map_per_condition = self.condition_amounts()?;
+        let total: f64 = map_per_condition.values().sum();
+
+        let cap = cap.clamp(0.0, 1.0);
+        let mut capped = HashSet::<String>::new();
+        let mut shares = HashMap::<String, f64>::new();
+        loop {
+            let free_share = 1.0 - cap * capped.len() as f64;
+            let free_total: f64 = map_per_condition
+                .iter()
+                .filter(|(k, _)| !capped.contains(*k))
+                .map(|(_, v)| v)
+                .sum();
+
+            shares.clear();
+            let mut changed = false;
+            for (name, value) in &map_per_condition {
+                let share = match capped.contains(name) {
+                    true => cap,
+                    false if free_total > 0.0 => value / free_total * free_share,
+                    false => 0.0,
+                };
+                if share > cap {
+                    capped.insert(name.clone());
+                    changed = true;
+                }
+                shares.insert(name.clone(), share.min(cap));
+            }
+            if !changed {
+                break;
+            }
+        }
+        if total == 0.0 {
+            // Keep the same NaN percentages as `describe` on empty amounts
+            shares.values_mut().for_each(|v| *v = f64::NAN);
+        }
+
+        Ok(percentage_table(GroupField::Condition.label(), shares, 1.0))
+    }
+
+    /// Consumes iterator of items and calculates total, mean, min and max
+    /// amount of them. See [`Summary`].
+    ///
+    /// Amount of items to take and skip can be managed
+    /// by [`Zomboid::set_take`] and [`Zomboid::set_skip`].
+    pub fn summary(&mut self) -> Result<Summary, E> {
+        let mut summary = Summary::default();
+        let mut count = 0u64;
+        for v in self.window() {
+            let amount = v?.amount;
+            summary.total += amount as u64;
+            summary.min = Some(summary.min.map_or(amount, |v| v.min(amount)));
+            summary.max = Some(summary.max.map_or(amount, |v| v.max(amount)));
+            count += 1;
+        }
+        if count > 0 {
+            summary.mean = summary.total as f64 / count as f64;
+        }
+        Ok(summary)
+    }
+
+    /// Consumes iterator of items and builds several summaries in a single pass:
+    /// percentage of amount per condition, total amount per type and
+    /// min / max / mean / median of the amount. See [`Report`].
+    ///
+    /// Amount of items to take and skip can be managed
+    /// by [`Zomboid::set_take`] and [`Zomboid::set_skip`].
+    pub fn report(&mut self) -> Result<Report, E> {
+        // The same accumulator as of `describe`, so the data is read once
+        let mut conditions = Describer::new();
+        let mut per_type = BTreeMap::<String, u64>::new();
+        let mut amounts = Vec::<u32>::new();
+
+        for v in self.window() {
+            let item = v?;
+            conditions.push(&item);
+            *per_type.entry(item.item_type).or_insert(0) += item.amount as u64;
+            amounts.push(item.amount);
+        }
+
+        let total: u64 = amounts.iter().map(|v| *v as u64).sum();
+        amounts.sort_unstable();
+        let median = match amounts.len() {
+            0 => 0.0,
+            n if n % 2 == 0 => (amounts[n / 2 - 1] as f64 + amounts[n / 2] as f64) / 2.0,
+            n => amounts[n / 2] as f64,
+        };
+        let mean = match amounts.len() {
+            0 => 0.0,
+            n => total as f64 / n as f64,
+        };
+
+        let metric = |name: &str, value: f64| Metric {
+            name: name.to_string(),
+            value,
+        };
+        let summary = vec![
+            metric("MIN", amounts.first().copied().unwrap_or(0) as f64),
+            metric("MAX", amounts.last().copied().unwrap_or(0) as f64),
+            metric("MEAN", mean),
+            metric("MEDIAN", median),
+        ];
+        let types = per_type
+            .into_iter()
+            .map(|(name, v)| metric(&name, v as f64))
+            .collect();
+
+        Ok(Report {
+            conditions: conditions.finish(),
+            types: Table::new(types)
+                .with_header(vec!["TYPE", "TOTAL"])
+                .with_width(40),
+            amounts: Table::new(summary)
+                .with_header(vec!["AMOUNT", "VALUE"])
+                .with_width(40),
+        })
+    }
+
+    /// Same as [`Zomboid::stream`] but merges items sharing the same id
+    /// and condition into a single row with summed amount. The sum saturates
+    /// at [`u32::MAX`].
+    ///
+    /// Items of the same id in different conditions are kept as separate rows.
+    /// Rows are ordered by the first occurrence of id and condition.
+    pub fn merge_by_id_keep_conditions(&mut self) -> Result<Table<Item>, E> {
+        let mut items = Vec::<Item>::new();
+        let mut index = HashMap::<(u32, Condition), usize>::new();
+
+        for v in self.window() {
+            let item = v?;
+            match index.get(&(item.id, item.condition.clone())) {
+                Some(&i) => items[i].amount = items[i].amount.saturating_add(item.amount),
+                None => {
+                    index.insert((item.id, item.condition.clone()), items.len());
+                    items.push(item);
+                }
+            }
+        }
+
+        Ok(Table::new(items).with_header(ITEM_HEADER.to_vec()))
+    }
+
+    /// Consumes iterator of items and builds a cross-tab of total amount
+    /// by item type (rows) and condition (columns).
+    ///
+    /// Header consists of the observed conditions, so the totals are buffered
+    /// until the iterator is consumed. Both types and conditions are sorted
+    /// alphabetically. Totals saturate at [`u32::MAX`].
+    ///
+    /// Amount of items to take and skip can be managed
+    /// by [`Zomboid::set_take`] and [`Zomboid::set_skip`].
+    pub fn pivot(&mut self) -> Result<Table<PivotRow>, E> {
+        let mut conditions = BTreeSet::<String>::new();
+        let mut per_type = BTreeMap::<String, HashMap<String, u32>>::new();
+
+        for v in self.window() {
+            let item = v?;
+            let total = per_type
+                .entry(item.item_type)
+                .or_default()
+                .entry(item.condition.to_string())
+                .or_insert(0);
+            *total = total.saturating_add(item.amount);
+            conditions.insert(item.condition.to_string());
+        }
+
+        let rows = per_type
+            .into_iter()
+            .map(|(item_type, totals)| PivotRow {
+                item_type,
+                totals: conditions
+                    .iter()
+                    .map(|c| totals.get(c).copied().unwrap_or(0))
+                    .collect(),
+            })
+            .collect();
+
+        let mut header = vec!["TYPE".to_string()];
+        header.extend(conditions);
+        Ok(Table::new(rows).with_header(header))
+    }
+
+    /// Consumes iterator of items and counts them.
+    ///
+    /// Amount of items to take and skip can be managed
+    /// by [`Zomboid::set_take`] and [`Zomboid::set_skip`].
+    pub fn count(&mut self) -> Result<usize, E> {
+        let mut count = 0;
+        for v in self.window() {
+            v?;
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    /// Consumes iterator of items and counts distinct item types.
+    ///
+    /// Amount of items to take and skip can be managed
+    /// by [`Zomboid::set_take`] and [`Zomboid::set_skip`].
+    pub fn distinct_type_count(&mut self) -> Result<usize, E> {
+        let mut types = HashSet::new();
+        for v in self.window() {
+            types.insert(v?.item_type);
+        }
+        Ok(types.len())
+    }
+
+    /// Consumes iterator of items and builds a table of `n` items with
+    /// the greatest amount, sorted descending. Items with equal amount
+    /// keep their input order.
+    ///
+    /// Only `n` items are held in memory at a time, so it's cheaper than
+    /// sorting the whole window when `n` is small.
+    ///
+    /// Amount of items to take and skip can be managed
+    /// by [`Zomboid::set_take`] and [`Zomboid::set_skip`].
+    pub fn top_n(&mut self, n: usize) -> Result<Table<Item>, E> {
+        // Min-heap, so the least of the kept items is replaced
+        let mut heap = BinaryHeap::<Reverse<Ranked>>::with_capacity(n);
+        for (seq, v) in self.window().enumerate() {
+            let ranked = Ranked { seq, item: v? };
+            if heap.len() < n {
+                heap.push(Reverse(ranked));
+            } else if heap.peek().is_some_and(|Reverse(least)| ranked > *least) {
+                heap.pop();
+                heap.push(Reverse(ranked));
+            }
+        }
+
+        let items = heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse(v)| v.item)
+            .collect();
+        Ok(Table::new(items).with_header(ITEM_HEADER.to_vec()))
+    }
+
+    /// Consumes iterator of items and counts distinct products, i.e. pairs
+    /// of name and type. Names are compared case-insensitively.
+    ///
+    /// Amount of items to take and skip can be managed
+    /// by [`Zomboid::set_take`] and [`Zomboid::set_skip`].
+    pub fn distinct_products(&mut self) -> Result<usize, E> {
+        let mut products = HashSet::new();
+        for v in self.window() {
+            let item = v?;
+            products.insert((item.name.to_lowercase(), item.item_type));
+        }
+        Ok(products.len())
+    }
+
+    /// Consumes iterator of items and returns ids from `expected`
+    /// that are absent in the data, in the order of `expected`.
+    ///
+    /// Amount of items to take and skip can be managed
+    /// by [`Zomboid::set_take`] and [`Zomboid::set_skip`].
+    pub fn missing_ids(&mut self, expected: &[u32]) -> Result<Vec<u32>, E> {
+        let mut present = HashSet::new();
+        for v in self.window() {
+            present.insert(v?.id);
+        }
+        Ok(expected
+            .iter()
+            .filter(|v| !present.contains(v))
+            .copied()
+            .collect())
+    }
+
+    /// Consumes iterator of items and hashes them into a single fingerprint.
+    ///
+    /// The fingerprint is order-sensitive, so the same items in a different
+    /// order produce a different value. It's 64-bit FNV-1a over [`Item::row_hash`]
+    /// of the items, same as of [`manifest`], so the same data yields the same
+    /// fingerprint across runs, platforms and Rust versions.
+    ///
+    /// Amount of items to take and skip can be managed
+    /// by [`Zomboid::set_take`] and [`Zomboid::set_skip`].
+    pub fn page_fingerprint(&mut self) -> Result<u64, E> {
+        let mut hash = model::FNV_OFFSET;
+        for v in self.window() {
+            hash = model::fnv1a(hash, v?.row_hash().as_bytes());
+        }
+        Ok(hash)
+    }
+
+    /// Consumes iterator of items and sends each of them to the channel
+    /// as soon as it's read.
+    ///
+    /// Sending stops after the first error, which is sent to the channel as well,
+    /// or when the receiving half of the channel is dropped.
+    ///
+    /// Amount of items to take and skip can be managed
+    /// by [`Zomboid::set_take`] and [`Zomboid::set_skip`].
+    pub fn stream_to_channel(&mut self, tx: Sender<Result<Item, E>>) {
+        for v in self.window() {
+            let is_err = v.is_err();
+            if tx.send(v).is_err() || is_err {
+                break;
+            }
+        }
+    }
+
+    /// Consumes iterator of items and calls `cb` for each of them,
+    /// keeping at least `per_item` delay between the calls.
+    ///
+    /// It's useful to drive a live display without flooding it. The delay
+    /// is done by sleeping the current thread.
+    ///
+    /// Amount of items to take and skip can be managed
+    /// by [`Zomboid::set_take`] and [`Zomboid::set_skip`].
+    pub fn stream_throttled(
+        &mut self,
+        per_item: Duration,
+        mut cb: impl FnMut(&Item),
+    ) -> Result<(), E> {
+        let mut last: Option<Instant> = None;
+        for v in self.window() {
+            let item = v?;
+            if let Some(last) = last {
+                thread::sleep(per_item.saturating_sub(last.elapsed()));
+            }
+            last = Some(Instant::now());
+            cb(&item);
+        }
+        Ok(())
+    }
+
+    /// Consumes iterator of items and yields a separate table each time
+    /// the value of `key` changes, so the source is expected to be sorted by it.
+    ///
+    /// Only the current section is held in memory. Sorting and merging
+    /// configured on [`Zomboid`] aren't applied. An error is yielded as soon
+    /// as it's read and the rows of the unfinished section are dropped.
+    ///
+    /// Amount of items to take and skip can be managed
+    /// by [`Zomboid::set_take`] and [`Zomboid::set_skip`].
+    pub fn stream_sections_by<K, F>(
+        self,
+        mut key: F,
+    ) -> impl Iterator<Item = Result<(K, Table<Item>), E>>
+    where
+        K: PartialEq,
+        F: FnMut(&Item) -> K,
+    {
+        let mut it = self.into_window();
+        let mut pending: Option<(K, Item)> = None;
+        std::iter::from_fn(move || {
+            let (k, first) = match pending.take() {
+                Some(v) => v,
+                None => match it.next()? {
+                    Ok(item) => (key(&item), item),
+                    Err(e) => return Some(Err(e)),
+                },
+            };
+            let mut rows = vec![first];
+            for v in it.by_ref() {
+                let item = match v {
+                    Ok(item) => item,
+                    Err(e) => return Some(Err(e)),
+                };
+                let next = key(&item);
+                if next != k {
+                    pending = Some((next, item));
+                    break;
+                }
+                rows.push(item);
+            }
+            Some(Ok((k, Table::new(rows).with_header(ITEM_HEADER.to_vec()))))
+        })
+    }
+
+    /// Returns the part of iterator bounded by the configured skip and take.
+    /// Errors are filtered out only in lenient mode.
+    fn window<'a>(&'a mut self) -> impl Iterator<Item = Result<Item, E>> + 'a
+    where
+        E: 'a,
+    {
+        self.window_filtered(|_| true)
+    }
+
+    /// Same as [`Zomboid::window`] but additionally keeps only items matching
+    /// `predicate`. Filtering is done before skip and take.
+    fn window_filtered<'a>(
+        &'a mut self,
+        predicate: impl Fn(&Item) -> bool + 'a,
+    ) -> impl Iterator<Item = Result<Item, E>> + 'a
+    where
+        E: 'a,
+    {
+        let keep = item_filter(self._below, self._condition.clone(), self._search.clone());
+        windowed(
+            self.it.by_ref(),
+            move |item| keep(item) && predicate(item),
+            self._lenient,
+            self._skip,
+            self._take,
+        )
+    }
+
+    /// Same as [`Zomboid::window`] but consumes `self`, so the window
+    /// can outlive it.
+    fn into_window(self) -> impl Iterator<Item = Result<Item, E>> {
+        let keep = item_filter(self._below, self._condition.clone(), self._search.clone());
+        windowed(self.it, keep, self._lenient, self._skip, self._take)
+    }
+}
+
+/// Keeps items of `source` matching `keep` and bounds them by `skip` and `take`.
+/// Errors are filtered out only if `lenient` is set.
+fn windowed<E>(
+    source: impl Iterator<Item = Result<Item, E>>,
+    keep: impl Fn(&Item) -> bool,
+    lenient: bool,
+    skip: Option<usize>,
+    take: Option<usize>,
+) -> impl Iterator<Item = Result<Item, E>> {
+    source
+        .filter(move |v| match v {
+            Ok(item) => keep(item),
+            Err(_) => !lenient,
+        })
+        .skip(skip.unwrap_or(0))
+        .take(take.unwrap_or(usize::MAX))
+}
+
+/// Combines the filters configured on [`Zomboid`], e.g.
+/// [`Zomboid::set_below`] and [`Zomboid::search`], into a single predicate.
+fn item_filter(
+    below: Option<u32>,
+    condition: Option<String>,
+    search: Option<String>,
+) -> impl Fn(&Item) -> bool {
+    move |item| {
+        below.is_none_or(|below| item.amount < below)
+            && condition
+                .as_ref()
+                .is_none_or(|c| item.condition.as_str().eq_ignore_ascii_case(c))
+            && search
+                .as_ref()
+                .is_none_or(|q| item.name.to_lowercase().contains(q))
+    }
+}
+
+impl<S, F, K, E> Zomboid<MergeSorted<S, F, K>>
+where
+    S: Iterator<Item = Result<Item, E>>,
+    F: Fn(&Item) -> K,
+    K: Ord,
+    E: std::error::Error,
+{
+    /// Creates [`Zomboid`] over several `sources` sorted by the same `key`.
+    ///
+    /// Sources are merged lazily, so the stream stays sorted by `key` without
+    /// buffering the whole data. See [`merge::MergeSorted`].
+    pub fn merge_sorted(sources: Vec<S>, key: F) -> Self {
+        Self::new(MergeSorted::new(sources, key))
+    }
+}
+
+/// Builds a table of percentage per group from the `map` of group values
+/// and their `total`. The first column is headed by `label`.
+fn percentage_table(label: &str, map: HashMap<String, f64>, total: f64) -> Table<Stat> {
+    let mut stats = Vec::<Stat>::with_capacity(map.len());
+    for (name, value) in map.into_iter() {
+        stats.push(Stat {
+            name,
+            value: value / total,
+        });
+    }
+
+    // Both columns share the same width, so size them to the widest
+    // group name if it doesn't fit into the default table width.
+    let cell = stats
+        .iter()
+        .map(|s| s.name.width())
+        .chain([label.width()])
+        .max()
+        .unwrap_or(0);
+
+    Table::new(stats)
+        .with_header([label, "%"])
+        .with_width(usize::max(40, 2 * (cell + 3)))
+}
+
+/// Item ranked by [`Zomboid::top_n`]. Greater amount ranks higher,
+/// and of equal amounts the one read earlier.
+struct Ranked {
+    seq: usize,
+    item: Item,
+}
+
+impl Ord for Ranked {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.item
+            .amount
+            .cmp(&other.item.amount)
+            .then(other.seq.cmp(&self.seq))
+    }
+}
+
+impl PartialOrd for Ranked {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Ranked {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Ranked {}
+
+fn escape_tsv(v: &str) -> String {
+    let mut s = String::with_capacity(v.len());
+    for c in v.chars() {
+        match c {
+            '\\' => s.push_str("\\\\"),
+            '\t' => s.push_str("\\t"),
+            '\n' => s.push_str("\\n"),
+            '\r' => s.push_str("\\r"),
+            c => s.push(c),
+        }
+    }
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        filter::FilterBuilder,
+        model::{Item, Stat},
+        table::Table,
+        AmountMode, Basis, Describer, GroupField, SortKey, Summary, Zomboid, ZomboidConfig,
+    };
+    use csv::Writer;
+    use std::collections::HashMap;
+    use std::error::Error;
+    use std::path::Path;
+    use std::path::PathBuf;
+    use std::time::{Duration, Instant};
+    use std::{fs, io};
+    use uuid::Uuid;
+
+    // TODO: use tempfile crate!
+    const BASE_PATH: &str = "~/.cache/rust/testing";
+
+    fn setup_csv() -> Result<PathBuf, Box<dyn Error>> {
+        fs::create_dir_all(BASE_PATH)?;
+        let path = format!("{}/{}.csv", BASE_PATH, Uuid::new_v4());
+        let mut writer = Writer::from_path(&path)?;
+
+        writer.write_record(["id", "name", "type", "condition", "amount"])?;
+        writer.write_record(["1", "Hummer", "Tool", "Mint", "10"])?;
+        writer.write_record(["2", "Nails", "Fasteners", "Good", "400"])?;
+        writer.write_record(["2", "Nails", "Fasteners", "Mint", "100"])?;
+        writer.write_record(["3", "Garden saw", "Tool", "New", "2"])?;
+        writer.write_record(["4", "Metal saw", "Tool", "New", "2"])?;
+
+        Ok(path.into())
+    }
+
+    fn teardown_csv(path: impl AsRef<Path>) -> io::Result<()> {
+        fs::remove_file(path)?;
+        Ok(())
+    }
+
+    #[ignore = "experiment"]
+    #[test]
+    fn csv_reader() {
+        let file_path = setup_csv().unwrap();
+        let mut r = csv::Reader::from_path(&file_path).unwrap();
+
+        for res in r.deserialize() {
+            let rec: Item = res.unwrap();
+            println!("{:?}", rec);
+        }
+
+        teardown_csv(file_path).unwrap();
+    }
+
+    #[test]
+    fn zomboid_csv() {
+        let file_path = setup_csv().unwrap();
+        let mut r = csv::Reader::from_path(&file_path).unwrap();
+        let mut z = Zomboid::new(r.deserialize());
+
+        z.set_take(Some(2));
+        z.set_skip(Some(2));
+
+        assert_eq!(z._skip, Some(2));
+        assert_eq!(z._take, Some(2));
+
+        let table = z.stream().unwrap();
+        let data = table.as_data();
+
+        assert_eq!(data.len(), 2);
+        assert_eq!(data[0].id, 2);
+        assert_eq!(data[1].id, 3);
+
+        println!("{}", table);
+
+        teardown_csv(file_path).unwrap();
+    }
+
+    #[test]
+    fn describe() {
+        let file_path = setup_csv().unwrap();
+        let mut r = csv::Reader::from_path(&file_path).unwrap();
+        let mut z = Zomboid::new(r.deserialize());
+
+        let table = z.describe().unwrap();
+        println!("{}", table);
+    }
+
+    fn sample_items() -> Vec<Item> {
+        let data = "id,name,type,condition,amount\n\
+                    1,Hummer,Tool,Mint,10\n\
+                    2,Nails,Fasteners,Good,400\n\
+                    2,Nails,Fasteners,Mint,100\n\
+                    3,Garden saw,Tool,New,2\n\
+                    4,Metal saw,Tool,New,2\n";
+        let mut r = csv::Reader::from_reader(data.as_bytes());
+        r.deserialize().map(|v| v.unwrap()).collect()
+    }
+
+    #[test]
+    fn page_fingerprint() {
+        let mut z = Zomboid::new(sample_items().into_iter().map(Ok::<_, io::Error>));
+        let first = z.page_fingerprint().unwrap();
+
+        let mut z = Zomboid::new(sample_items().into_iter().map(Ok::<_, io::Error>));
+        let second = z.page_fingerprint().unwrap();
+
+        let mut z = Zomboid::new(sample_items().into_iter().rev().map(Ok::<_, io::Error>));
+        let reordered = z.page_fingerprint().unwrap();
+
+        assert_eq!(first, second);
+        assert_ne!(first, reordered);
+
+        // Same FNV-1a fold as of the manifest
+        let items = sample_items().into_iter().map(Ok::<_, io::Error>);
+        let manifest = crate::manifest([("page".to_string(), items)]).unwrap();
+        assert_eq!(format!("{:016x}", first), manifest[0].2);
+    }
+
+    #[test]
+    fn describe_fits_widest_condition() {
+        let mut items = sample_items();
+        items[0].condition = "Requires maintenance".into();
+
+        let mut z = Zomboid::new(items.into_iter().map(Ok::<_, io::Error>));
+        let table = format!("{}", z.describe().unwrap());
+
+        // Padding is a single space on both sides of the cell
+        let expected = "Requires maintenance".len() + 2;
+        for row in table.lines().skip(1).filter(|v| v.starts_with('│')) {
+            let cell = row.split('│').nth(1).unwrap();
+            assert_eq!(cell.chars().count(), expected);
+        }
+    }
+
+    #[test]
+    fn percentage_table_fits_non_ascii_label() {
+        let label = "ÄÖÜ".repeat(8);
+        let map = HashMap::from([("Mint".to_string(), 1.0)]);
+        let table = crate::percentage_table(&label, map, 1.0).to_string();
+
+        // Label is as wide as its chars, not its bytes
+        let expected = label.chars().count() + 2;
+        for row in table.lines().filter(|v| v.starts_with('│')) {
+            let cell = row.split('│').nth(1).unwrap();
+            assert_eq!(cell.chars().count(), expected);
+        }
+    }
+
+    #[test]
+    fn stream_to_channel() {
+        let (tx, rx) = std::sync::mpsc::channel();
+        let mut z = Zomboid::new(sample_items().into_iter().map(Ok::<_, io::Error>));
+        z.set_take(Some(2));
+        z.set_skip(Some(1));
+
+        z.stream_to_channel(tx);
+        let received: Vec<Item> = rx.iter().map(|v| v.unwrap()).collect();
+
+        assert_eq!(received, sample_items()[1..3]);
+    }
+
+    #[test]
+    fn stream_retry() {
+        let flaky = || {
+            let mut items: Vec<io::Result<Item>> = sample_items().into_iter().map(Ok).collect();
+            items.insert(2, Err(io::ErrorKind::Interrupted.into()));
+            items
+        };
+        let is_transient = |e: &io::Error| e.kind() == io::ErrorKind::Interrupted;
+
+        let mut z = Zomboid::new(flaky().into_iter());
+        let table = z.stream_retry(1, is_transient).unwrap();
+        assert_eq!(*table.as_data(), sample_items());
+
+        let mut z = Zomboid::new(flaky().into_iter());
+        assert!(z.stream_retry(0, is_transient).is_err());
+
+        let mut z = Zomboid::new(flaky().into_iter());
+        assert!(z.stream_retry(1, |_| false).is_err());
+    }
+
+    #[test]
+    fn stream_retry_filters() {
+        let mut items: Vec<io::Result<Item>> = sample_items().into_iter().map(Ok).collect();
+        items.insert(1, Err(io::ErrorKind::Interrupted.into()));
+        let is_transient = |e: &io::Error| e.kind() == io::ErrorKind::Interrupted;
+
+        let mut z = Zomboid::new(items.into_iter());
+        z.filter_condition(Some("new"));
+        z.search(Some("SAW".into()));
+        z.sort_by(SortKey::Id, false);
+        let table = z.stream_retry(1, is_transient).unwrap();
+
+        let ids: Vec<u32> = table.as_data().iter().map(|v| v.id).collect();
+        assert_eq!(ids, vec![4, 3]);
+    }
+
+    #[test]
+    fn stream_with_running_total() {
+        let mut z = Zomboid::new(sample_items().into_iter().map(Ok::<_, io::Error>));
+        z.set_skip(Some(1));
+        z.set_take(Some(3));
+
+        let table = z.stream_with_running_total().unwrap();
+        let data = table.as_data();
+        let total: u64 = data.iter().map(|v| v.item.amount as u64).sum();
+
+        assert_eq!(data[0].running, 400);
+        assert_eq!(data.last().unwrap().running, total);
+        println!("{}", table);
+    }
+
+    #[test]
+    fn distinct_type_count() {
+        let mut z = Zomboid::new(sample_items().into_iter().map(Ok::<_, io::Error>));
+        assert_eq!(z.distinct_type_count().unwrap(), 2);
+    }
+
+    #[test]
+    fn describer() {
+        let mut describer = Describer::new();
+        for item in sample_items() {
+            describer.push(&item);
+        }
+        let mut table = describer.finish();
+
+        let mut z = Zomboid::new(sample_items().into_iter().map(Ok::<_, io::Error>));
+        let mut expected = z.describe().unwrap();
+        table.sort_by_value(true);
+        expected.sort_by_value(true);
+        let rows = |t: &Table<Stat>| {
+            t.as_data()
+                .iter()
+                .map(|v| (v.name.clone(), v.value))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(rows(&table), rows(&expected));
+        assert!((table.as_data()[0].value - 400.0 / 514.0).abs() < 1e-9);
+
+        let mut describer = Describer::grouped(GroupField::Type, Basis::Count);
+        sample_items().iter().for_each(|v| describer.push(v));
+        let table = describer.finish();
+        let tools = table.as_data().iter().find(|v| v.name == "Tool").unwrap();
+        assert!((tools.value - 0.6).abs() < 1e-9);
+    }
+
+    #[test]
+    fn stat_delta() {
+        let stat = |name: &str, value: f64| Stat {
+            name: name.to_string(),
+            value,
+        };
+        let old = Table::new(vec![stat("Mint", 0.2), stat("Good", 0.5), stat("New", 0.3)]);
+        let new = Table::new(vec![stat("Mint", 0.3), stat("Good", 0.7)]);
+        let delta = crate::stat_delta(&old, &new);
+
+        let rows: Vec<(&str, f64)> = delta
+            .as_data()
+            .iter()
+            .map(|v| (v.name.as_str(), v.value))
+            .collect();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0].0, "Mint");
+        assert!((rows[0].1 - 0.1).abs() < 1e-9);
+        assert!((rows[1].1 - 0.2).abs() < 1e-9);
+        assert_eq!(rows[2].0, "New");
+        assert!((rows[2].1 + 0.3).abs() < 1e-9);
+        assert!(delta.to_string().contains("10.0%"));
+    }
+
+    #[test]
+    fn distinct_products() {
+        let mut z = Zomboid::new(sample_items().into_iter().map(Ok::<_, io::Error>));
+        assert_eq!(z.distinct_products().unwrap(), 4);
+
+        let mut items = sample_items();
+        items[0].name = "NAILS".into();
+        items[0].item_type = "Fasteners".into();
+        let mut z = Zomboid::new(items.into_iter().map(Ok::<_, io::Error>));
+        z.set_take(Some(3));
+        assert_eq!(z.distinct_products().unwrap(), 1);
+    }
+
+    #[test]
+    fn top_n() {
+        let mut z = Zomboid::new(sample_items().into_iter().map(Ok::<_, io::Error>));
+        let table = z.top_n(2).unwrap();
+        let amounts: Vec<u32> = table.as_data().iter().map(|v| v.amount).collect();
+        assert_eq!(amounts, vec![400, 100]);
+
+        // Ties keep the input order
+        let mut z = Zomboid::new(sample_items().into_iter().map(Ok::<_, io::Error>));
+        let table = z.top_n(4).unwrap();
+        let ids: Vec<u32> = table.as_data().iter().map(|v| v.id).collect();
+        assert_eq!(ids, vec![2, 2, 1, 3]);
+
+        let mut z = Zomboid::new(sample_items().into_iter().map(Ok::<_, io::Error>));
+        assert!(z.top_n(0).unwrap().as_data().is_empty());
+    }
+
+    #[test]
+    fn condition_counts() {
+        let mut z = Zomboid::new(sample_items().into_iter().map(Ok::<_, io::Error>));
+        let counts = z.condition_counts().unwrap();
+
+        assert_eq!(counts.len(), 3);
+        assert_eq!(counts["Mint"], 110);
+        assert_eq!(counts["Good"], 400);
+        assert_eq!(counts["New"], 4);
+    }
+
+    #[test]
+    fn condition_sums_past_u32() {
+        let items = || {
+            let mut items = sample_items();
+            items.iter_mut().for_each(|v| v.amount = u32::MAX);
+            items.into_iter().map(Ok::<_, io::Error>)
+        };
+
+        let mut z = Zomboid::new(items());
+        assert_eq!(z.condition_counts().unwrap()["Mint"], u32::MAX);
+
+        let shares = |table: Table<Stat>| {
+            let mut shares: Vec<(String, f64)> = table
+                .into_data()
+                .into_iter()
+                .map(|v| (v.name, v.value))
+                .collect();
+            shares.sort_by(|a, b| a.0.cmp(&b.0));
+            shares
+        };
+        let described = shares(Zomboid::new(items()).describe().unwrap());
+        let by_condition = shares(
+            Zomboid::new(items())
+                .describe_by(GroupField::Condition)
+                .unwrap(),
+        );
+        assert_eq!(described, by_condition);
+        assert!((described[0].1 - 0.2).abs() < 1e-9);
+
+        // Good 1, Mint 2, New 2 of 5 items
+        let expected = -[0.2f64, 0.4, 0.4].iter().map(|p| p * p.log2()).sum::<f64>();
+        let entropy = Zomboid::new(items()).condition_entropy().unwrap();
+        assert!((entropy - expected).abs() < 1e-9);
+
+        let capped = shares(Zomboid::new(items()).describe_capped(0.35).unwrap());
+        assert_eq!(capped[1].1, 0.35);
+        assert!((capped[0].1 - 0.3).abs() < 1e-9);
+    }
+
+    #[test]
+    fn reset() {
+        let mut z = Zomboid::new(sample_items().into_iter().map(Ok::<_, io::Error>));
+        z.set_take(Some(1));
+        z.set_skip(Some(0));
+        z.set_below(Some(500));
+        z.set_lenient(true);
+        z.filter_condition(Some("mint"));
+        z.search(Some("nails".into()));
+        z.sort_by(SortKey::Amount, false);
+        z.merge_by_id(true);
+        z.set_max_rows(Some(5));
+        assert_eq!(z.stream().unwrap().as_data()[0].id, 2);
+
+        z.reset();
+        assert_eq!(z._take, None);
+        assert_eq!(z._skip, None);
+        assert_eq!(z._below, None);
+        assert!(!z._lenient);
+        assert_eq!(z._condition, None);
+        assert_eq!(z._search, None);
+        assert_eq!(z._sort, None);
+        assert!(!z._merge_by_id);
+        assert_eq!(z._max_rows, None);
+
+        // The iterator isn't rewound
+        let ids: Vec<u32> = z.stream().unwrap().as_data().iter().map(|v| v.id).collect();
+        assert_eq!(ids, vec![3, 4]);
+    }
+
+    #[test]
+    fn stream_footer() {
+        let mut z = Zomboid::new(sample_items().into_iter().map(Ok::<_, io::Error>));
+        let table = z.stream().unwrap().to_string();
+        let rows: Vec<&str> = table.lines().collect();
+
+        // top, header, mid, 5 items, mid, footer, bottom
+        assert_eq!(rows.len(), 11);
+        assert!(rows[8].starts_with('├'));
+        assert!(rows[9].contains("TOTAL"));
+        let amount = rows[9].rsplit('│').nth(1).unwrap().trim();
+        assert_eq!(amount, "514");
+    }
+
+    #[test]
+    fn stream_to() {
+        let mut z = Zomboid::new(sample_items().into_iter().map(Ok::<_, io::Error>));
+        let table = z.stream().unwrap();
+
+        let mut z = Zomboid::new(sample_items().into_iter().map(Ok::<_, io::Error>));
+        let mut out = Vec::new();
+        z.stream_to(&mut out).unwrap();
+
+        assert_eq!(String::from_utf8(out).unwrap(), format!("{}\n", table));
+    }
+
+    #[test]
+    fn stream_sections_by() {
+        let mut items = sample_items();
+        items.sort_by(|a, b| b.item_type.cmp(&a.item_type));
+        let z = Zomboid::new(items.into_iter().map(Ok::<_, io::Error>));
+
+        let sections: Vec<(String, Table<Item>)> = z
+            .stream_sections_by(|v| v.item_type.clone())
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        assert_eq!(sections.len(), 2);
+        assert_eq!(sections[0].0, "Tool");
+        assert_eq!(sections[0].1.as_data().len(), 3);
+        assert_eq!(sections[1].0, "Fasteners");
+        assert_eq!(sections[1].1.as_data().len(), 2);
+    }
+
+    #[test]
+    fn csv_flexible() {
+        fs::create_dir_all(BASE_PATH).unwrap();
+        let path = format!("{}/{}.csv", BASE_PATH, Uuid::new_v4());
+        fs::write(
+            &path,
+            "id,name,type,condition,amount\n\
+             1,Hummer,Tool,Mint,10\n\
+             2,Nails,Fasteners,Good\n\
+             3,Garden saw,Tool,New,2,Shed\n",
+        )
+        .unwrap();
+
+        let mut z = Zomboid::new(crate::csv_flexible(&path).unwrap());
+        let table = z.stream().unwrap();
+        let data = table.as_data();
+
+        assert_eq!(data.len(), 3);
+        assert_eq!(data[1].amount, 0);
+        assert_eq!(data[2].amount, 2);
+
+        teardown_csv(path).unwrap();
+
+        // Other readers still require the amount
+        let data = "id,name,type,condition\n1,Hummer,Tool,Mint\n";
+        let mut r = csv::Reader::from_reader(data.as_bytes());
+        assert!(r.deserialize::<Item>().next().unwrap().is_err());
+    }
+
+    #[test]
+    fn pivot() {
+        let mut z = Zomboid::new(sample_items().into_iter().map(Ok::<_, io::Error>));
+        let table = z.pivot().unwrap();
+        let data = table.as_data();
+
+        // Columns are Good, Mint, New
+        assert_eq!(data.len(), 2);
+        assert_eq!(data[0].item_type, "Fasteners");
+        assert_eq!(data[0].totals, vec![400, 100, 0]);
+        assert_eq!(data[1].item_type, "Tool");
+        assert_eq!(data[1].totals, vec![0, 10, 4]);
+        println!("{}", table);
+
+        let mut items = sample_items();
+        items[3].amount = u32::MAX - 1;
+        let mut z = Zomboid::new(items.into_iter().map(Ok::<_, io::Error>));
+        let table = z.pivot().unwrap();
+        assert_eq!(table.as_data()[1].totals, vec![0, 10, u32::MAX]);
+    }
+
+    #[test]
+    fn with_config() {
+        let mut items: Vec<io::Result<Item>> = sample_items().into_iter().map(Ok).collect();
+        items.insert(1, Err(io::ErrorKind::InvalidData.into()));
+
+        let config = ZomboidConfig {
+            take: Some(3),
+            skip: Some(1),
+            max_rows: Some(2),
+            lenient: true,
+            sort: Some((SortKey::Amount, true)),
+        };
+        let mut z = Zomboid::with_config(items.into_iter(), config);
+        let table = z.stream().unwrap();
+        let amounts: Vec<u32> = table.as_data().iter().map(|v| v.amount).collect();
+
+        // Items 2 to 4 are taken, sorted and then limited to 2 rows
+        assert_eq!(amounts, vec![2, 100]);
+    }
+
+    #[test]
+    fn merge_by_id_keep_conditions() {
+        let mut items = sample_items();
+        items.push(items[1].clone());
+
+        let mut z = Zomboid::new(items.into_iter().map(Ok::<_, io::Error>));
+        let table = z.merge_by_id_keep_conditions().unwrap();
+        let data = table.as_data();
+
+        assert_eq!(data.len(), 5);
+        assert_eq!((data[1].id, data[1].condition.as_str()), (2, "Good"));
+        assert_eq!(data[1].amount, 800);
+        assert_eq!((data[2].id, data[2].condition.as_str()), (2, "Mint"));
+        assert_eq!(data[2].amount, 100);
+
+        let mut items = sample_items();
+        items[1].amount = u32::MAX - 1;
+        items.push(items[1].clone());
+        let mut z = Zomboid::new(items.into_iter().map(Ok::<_, io::Error>));
+        let table = z.merge_by_id_keep_conditions().unwrap();
+        assert_eq!(table.as_data()[1].amount, u32::MAX);
+    }
+
+    #[test]
+    fn stream_tsv() {
+        let mut buf = Vec::new();
+        let mut z = Zomboid::new(sample_items().into_iter().map(Ok::<_, io::Error>));
+        z.stream_tsv(&mut buf).unwrap();
+
+        let mut r = csv::ReaderBuilder::new()
+            .delimiter(b'\t')
+            .quoting(false)
+            .from_reader(buf.as_slice());
+        let items: Vec<Item> = r.deserialize().map(|v| v.unwrap()).collect();
+        assert_eq!(items, sample_items());
+
+        let mut item = sample_items().remove(0);
+        item.name = "Hummer\tbig\nheavy".into();
+        let mut buf = Vec::new();
+        let mut z = Zomboid::new([Ok::<_, io::Error>(item)].into_iter());
+        z.stream_tsv(&mut buf).unwrap();
+
+        let out = String::from_utf8(buf).unwrap();
+        assert_eq!(
+            out.lines().nth(1),
+            Some("1\tHummer\\tbig\\nheavy\tTool\tMint\t10")
+        );
+    }
+
+    #[test]
+    fn describe_with() {
+        let percentages = |basis: Basis| -> HashMap<String, f64> {
+            let mut z = Zomboid::new(sample_items().into_iter().map(Ok::<_, io::Error>));
+            let table = z.describe_with(basis).unwrap();
+            table
+                .as_data()
+                .iter()
+                .map(|v| (v.name.clone(), v.value))
+                .collect()
+        };
+
+        let amount = percentages(Basis::Amount);
+        assert!((amount["Mint"] - 110.0 / 514.0).abs() < 1e-9);
+
+        let count = percentages(Basis::Count);
+        assert!((count["Mint"] - 0.4).abs() < 1e-9);
+        assert!((count["Good"] - 0.2).abs() < 1e-9);
+
+        let weights = HashMap::from([("Good".to_string(), 0.5)]);
+        let weighted = percentages(Basis::Weighted(weights));
+        assert!((weighted["Good"] - 200.0 / 314.0).abs() < 1e-9);
+        assert!((weighted["New"] - 4.0 / 314.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn merge_sorted() {
+        let (odd, even): (Vec<Item>, Vec<Item>) = (1..=6)
+            .map(|id| Item {
+                id,
+                ..sample_items().remove(0)
+            })
+            .partition(|v| v.id % 2 == 1);
+
+        let sources = vec![
+            odd.into_iter().map(Ok::<_, io::Error>),
+            even.into_iter().map(Ok::<_, io::Error>),
+        ];
+        let mut z = Zomboid::merge_sorted(sources, |v| v.id);
+        let table = z.stream().unwrap();
+        let ids: Vec<u32> = table.as_data().iter().map(|v| v.id).collect();
+
+        assert_eq!(ids, vec![1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn stream_filtered() {
+        let mut z = Zomboid::new(sample_items().into_iter().map(Ok::<_, io::Error>));
+        let filter = FilterBuilder::new()
+            .condition("mint")
+            .min_amount(50)
+            .build();
+
+        let table = z.stream_filtered(filter).unwrap();
+        let data = table.as_data();
+
+        assert_eq!(data.len(), 1);
+        assert_eq!((data[0].id, data[0].amount), (2, 100));
+    }
+
+    #[test]
+    fn compress_runs() {
+        let mut items = sample_items();
+        let hummer = items[0].clone();
+        items.splice(0..0, [hummer.clone(), hummer]);
+
+        let mut z = Zomboid::new(items.into_iter().map(Ok::<_, io::Error>));
+        let table = z.compress_runs().unwrap();
+        let data = table.as_data();
+
+        assert_eq!(data.len(), 5);
+        assert_eq!((data[0].item.id, data[0].count), (1, 3));
+        assert!(data[1..].iter().all(|v| v.count == 1));
+    }
+
+    #[test]
+    fn stream_throttled() {
+        let per_item = Duration::from_millis(20);
+        let mut z = Zomboid::new(sample_items().into_iter().map(Ok::<_, io::Error>));
+        z.set_take(Some(3));
+
+        let mut ids = Vec::new();
+        let start = Instant::now();
+        z.stream_throttled(per_item, |v| ids.push(v.id)).unwrap();
+
+        assert_eq!(ids, vec![1, 2, 2]);
+        assert!(start.elapsed() >= per_item * 2);
+    }
+
+    #[test]
+    fn limited_reader() {
+        let data = "id,name,type,condition,amount\n\
+                    1,Hummer,Tool,Mint,10\n\
+                    2,Nails,Fasteners,Good,400\n";
+
+        let r = crate::limited_reader(data.as_bytes(), 40);
+        let mut r = csv::Reader::from_reader(r);
+        let mut z = Zomboid::new(r.deserialize());
+        let err = z.stream().unwrap_err();
+        assert!(err.to_string().contains("exceeds the limit of 40 bytes"));
+
+        let r = crate::limited_reader(data.as_bytes(), data.len() as u64);
+        let mut r = csv::Reader::from_reader(r);
+        let mut z = Zomboid::new(r.deserialize());
+        assert_eq!(z.stream().unwrap().as_data().len(), 2);
+    }
+
+    #[test]
+    fn stream_annotated() {
+        let mut items = sample_items();
+        items[3].amount = 0;
+
+        let mut z = Zomboid::new(items.into_iter().map(Ok::<_, io::Error>));
+        z.set_skip(Some(1));
+        let table = z.stream_annotated(|v| v.amount > 0).unwrap();
+        let valid: Vec<bool> = table.as_data().iter().map(|v| v.valid).collect();
+
+        assert_eq!(valid, vec![true, true, false, true]);
+        assert!(format!("{}", table).contains('✗'));
+    }
+
+    #[test]
+    fn stream_until() {
+        let slow = sample_items().into_iter().map(|v| {
+            std::thread::sleep(Duration::from_millis(20));
+            Ok::<_, io::Error>(v)
+        });
+        let mut z = Zomboid::new(slow);
+
+        let table = z
+            .stream_until(Instant::now() + Duration::from_millis(30))
+            .unwrap();
+        let len = table.as_data().len();
+
+        assert!(len > 0 && len < 5);
+
+        let mut z = Zomboid::new(sample_items().into_iter().map(Ok::<_, io::Error>));
+        z.merge_by_id(true);
+        z.sort_by(SortKey::Amount, false);
+        z.set_max_rows(Some(2));
+        let table = z
+            .stream_until(Instant::now() + Duration::from_secs(60))
+            .unwrap();
+        let rows: Vec<(u32, u32)> = table.as_data().iter().map(|v| (v.id, v.amount)).collect();
+
+        assert_eq!(rows, vec![(2, 500), (1, 10)]);
+        assert!(table.to_string().contains("TOTAL"));
+    }
+
+    #[test]
+    fn missing_ids() {
+        let mut z = Zomboid::new(sample_items().into_iter().map(Ok::<_, io::Error>));
+        assert_eq!(z.missing_ids(&[1, 2, 3, 4, 5]).unwrap(), vec![5]);
+
+        let mut z = Zomboid::new(sample_items().into_iter().map(Ok::<_, io::Error>));
+        z.set_take(Some(2));
+        assert_eq!(z.missing_ids(&[1, 2, 3, 4, 5]).unwrap(), vec![3, 4, 5]);
+    }
+
+    #[test]
+    fn report() {
+        let mut z = Zomboid::new(sample_items().into_iter().map(Ok::<_, io::Error>));
+        let report = z.report().unwrap();
+
+        let types: Vec<(&str, f64)> = report
+            .types
+            .as_data()
+            .iter()
+            .map(|v| (v.name.as_str(), v.value))
+            .collect();
+        assert_eq!(types, vec![("Fasteners", 500.0), ("Tool", 14.0)]);
+
+        let amounts: Vec<f64> = report.amounts.as_data().iter().map(|v| v.value).collect();
+        assert_eq!(amounts, vec![2.0, 400.0, 514.0 / 5.0, 10.0]);
+        let sorted = |table: &Table<Stat>| {
+            let mut stats: Vec<(String, f64)> = table
+                .as_data()
+                .iter()
+                .map(|v| (v.name.clone(), v.value))
+                .collect();
+            stats.sort_by(|a, b| a.0.cmp(&b.0));
+            stats
+        };
+        let mut z = Zomboid::new(sample_items().into_iter().map(Ok::<_, io::Error>));
+        assert_eq!(sorted(&report.conditions), sorted(&z.describe().unwrap()));
+    }
+
+    #[test]
+    fn dedup_by_key() {
+        let mut z = Zomboid::new(sample_items().into_iter().map(Ok::<_, io::Error>));
+        let table = z.dedup_by_key(|v| (v.id, v.condition.clone())).unwrap();
+        assert_eq!(table.as_data().len(), 5);
+
+        let twos: Vec<&str> = table
+            .as_data()
+            .iter()
+            .filter(|v| v.id == 2)
+            .map(|v| v.condition.as_str())
+            .collect();
+        assert_eq!(twos, vec!["Good", "Mint"]);
+
+        let mut z = Zomboid::new(sample_items().into_iter().map(Ok::<_, io::Error>));
+        let table = z.dedup_by_key(|v| v.id).unwrap();
+        assert_eq!(table.as_data().len(), 4);
+    }
+
+    #[test]
+    fn csv_float_amounts() {
+        let data = "id,name,type,condition,amount\n1,Hummer,Tool,Mint,10.6\n";
+        let parse = |mode| {
+            let r = csv::Reader::from_reader(data.as_bytes());
+            let mut it = super::csv_float_amounts(r, mode).unwrap();
+            it.next().unwrap().map(|v| v.amount)
+        };
+
+        assert_eq!(parse(AmountMode::Round).unwrap(), 11);
+        assert_eq!(parse(AmountMode::Truncate).unwrap(), 10);
+        assert!(parse(AmountMode::Strict).is_err());
+    }
+
+    #[test]
+    fn describe_capped() {
+        let mut z = Zomboid::new(sample_items().into_iter().map(Ok::<_, io::Error>));
+        let table = z.describe_capped(0.5).unwrap();
+        let shares: HashMap<&str, f64> = table
+            .as_data()
+            .iter()
+            .map(|v| (v.name.as_str(), v.value))
+            .collect();
+
+        // Good has 400 of 514, far over the cap
+        assert_eq!(shares["Good"], 0.5);
+        // The rest is renormalized 110:4
+        assert!((shares["Mint"] - 0.5 * 110.0 / 114.0).abs() < 1e-9);
+        assert!((shares.values().sum::<f64>() - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn remap_types() {
+        let map = HashMap::from([("Tool".to_string(), "Tools".to_string())]);
+        let mut z = Zomboid::new(sample_items().into_iter().map(Ok::<_, io::Error>));
+        let table = z.remap_types(&map).unwrap();
+
+        let types: Vec<&str> = table
+            .as_data()
+            .iter()
+            .map(|v| v.item_type.as_str())
+            .collect();
+        assert_eq!(
+            types,
+            vec!["Tools", "Fasteners", "Fasteners", "Tools", "Tools"]
+        );
+
+        let mut z = Zomboid::new(sample_items().into_iter().map(Ok::<_, io::Error>));
+        z.sort_by(SortKey::Type, false);
+        z.set_max_rows(Some(3));
+        let table = z.remap_types(&map).unwrap();
+        let types: Vec<&str> = table
+            .as_data()
+            .iter()
+            .map(|v| v.item_type.as_str())
+            .collect();
+
+        assert_eq!(types, vec!["Tools", "Tools", "Tools"]);
+        assert!(table.to_string().contains("TOTAL"));
+    }
+
+    #[test]
+    fn filter_condition() {
+        let mut z = Zomboid::new(sample_items().into_iter().map(Ok::<_, io::Error>));
+        z.filter_condition(Some("mint"));
+        let table = z.stream().unwrap();
+        let ids: Vec<u32> = table.as_data().iter().map(|v| v.id).collect();
+        assert_eq!(ids, vec![1, 2]);
+
+        let mut z = Zomboid::new(sample_items().into_iter().map(Ok::<_, io::Error>));
+        z.filter_condition(Some("New"));
+        z.set_skip(Some(1));
+        let table = z.stream().unwrap();
+        let ids: Vec<u32> = table.as_data().iter().map(|v| v.id).collect();
+        assert_eq!(ids, vec![4]);
+
+        let mut z = Zomboid::new(sample_items().into_iter().map(Ok::<_, io::Error>));
+        z.filter_condition(Some("Good"));
+        z.filter_condition(None);
+        assert_eq!(z.stream().unwrap().as_data().len(), 5);
+    }
+
+    #[test]
+    fn search() {
+        let mut z = Zomboid::new(sample_items().into_iter().map(Ok::<_, io::Error>));
+        z.search(Some("saw".into()));
+        let table = z.stream().unwrap();
+        let names: Vec<&str> = table.as_data().iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(names, vec!["Garden saw", "Metal saw"]);
+
+        let mut z = Zomboid::new(sample_items().into_iter().map(Ok::<_, io::Error>));
+        z.search(Some("NAILS".into()));
+        z.filter_condition(Some("Mint"));
+        let table = z.stream().unwrap();
+        let ids: Vec<u32> = table.as_data().iter().map(|v| v.id).collect();
+        assert_eq!(ids, vec![2]);
+
+        let mut z = Zomboid::new(sample_items().into_iter().map(Ok::<_, io::Error>));
+        z.search(Some("saw".into()));
+        z.set_skip(Some(1));
+        let table = z.stream().unwrap();
+        assert_eq!(table.as_data()[0].name, "Metal saw");
+    }
+
+    #[test]
+    fn describe_pareto() {
+        let mut z = Zomboid::new(sample_items().into_iter().map(Ok::<_, io::Error>));
+        let table = z.describe_pareto().unwrap();
+        let rows = table.as_data();
+
+        let names: Vec<&str> = rows.iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(names, vec!["Good", "Mint", "New"]);
+        assert!((rows[1].cumulative - 510.0 / 514.0).abs() < 1e-9);
+        assert!((rows.last().unwrap().cumulative - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn sort_by() {
+        let mut z = Zomboid::new(sample_items().into_iter().map(Ok::<_, io::Error>));
+        z.sort_by(SortKey::Amount, false);
+        let table = z.stream().unwrap();
+        let amounts: Vec<u32> = table.as_data().iter().map(|v| v.amount).collect();
+        assert_eq!(amounts, vec![400, 100, 10, 2, 2]);
+
+        // Equal amounts keep the input order
+        let names: Vec<&str> = table.as_data().iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(names[3..], ["Garden saw", "Metal saw"]);
+
+        let mut z = Zomboid::new(sample_items().into_iter().map(Ok::<_, io::Error>));
+        z.sort_by(SortKey::Type, true);
+        z.set_take(Some(3));
+        let table = z.stream().unwrap();
+        let ids: Vec<u32> = table.as_data().iter().map(|v| v.id).collect();
+        assert_eq!(ids, vec![2, 2, 1]);
+    }
+
+    #[test]
+    fn sort_by_condition_severity() {
+        let items = ["Worn", "Broken", "New", "Mint"].map(|c| {
+            let mut item = sample_items().remove(0);
+            item.condition = c.into();
+            Ok::<_, io::Error>(item)
+        });
+        let mut z = Zomboid::new(items.into_iter());
+        z.sort_by(SortKey::Condition, true);
+        let table = z.stream().unwrap();
+        let conditions: Vec<&str> = table
+            .as_data()
+            .iter()
+            .map(|v| v.condition.as_str())
+            .collect();
+
+        assert_eq!(conditions, ["Mint", "New", "Worn", "Broken"]);
+    }
+
+    #[test]
+    fn summary() {
+        let mut z = Zomboid::new(sample_items().into_iter().map(Ok::<_, io::Error>));
+        let summary = z.summary().unwrap();
+        assert_eq!(
+            summary,
+            Summary {
+                total: 514,
+                mean: 514.0 / 5.0,
+                min: Some(2),
+                max: Some(400),
+            }
+        );
+
+        let mut z = Zomboid::new(std::iter::empty::<Result<Item, io::Error>>());
+        assert_eq!(z.summary().unwrap(), Summary::default());
+    }
+
+    #[test]
+    fn dedup_by_id_report() {
+        let mut z = Zomboid::new(sample_items().into_iter().map(Ok::<_, io::Error>));
+        let (table, dropped) = z.dedup_by_id_report().unwrap();
+
+        assert_eq!(dropped, 1);
+        let ids: Vec<u32> = table.as_data().iter().map(|v| v.id).collect();
+        assert_eq!(ids, vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn stream_sorted_multi() {
+        let mut z = Zomboid::new(sample_items().into_iter().rev().map(Ok::<_, io::Error>));
+        let table = z
+            .stream_sorted_multi(&[SortKey::Id, SortKey::Condition], &[false, true])
+            .unwrap();
+        let rows: Vec<(u32, &str)> = table
+            .as_data()
+            .iter()
+            .map(|v| (v.id, v.condition.as_str()))
+            .collect();
+
+        assert_eq!(
+            rows,
+            vec![
+                (1, "Mint"),
+                // Descending by severity, i.e. the worst first
+                (2, "Good"),
+                (2, "Mint"),
+                (3, "New"),
+                (4, "New")
+            ]
+        );
+
+        let mut z = Zomboid::new(sample_items().into_iter().map(Ok::<_, io::Error>));
+        z.sort_by(SortKey::Id, true);
+        z.merge_by_id(true);
+        z.set_max_rows(Some(2));
+        let table = z.stream_sorted_multi(&[SortKey::Amount], &[true]).unwrap();
+        let rows: Vec<(u32, u32)> = table.as_data().iter().map(|v| (v.id, v.amount)).collect();
+
+        assert_eq!(rows, vec![(2, 500), (1, 10)]);
+        assert!(table.to_string().contains("TOTAL"));
+    }
+
+    #[test]
+    fn describe_by_type() {
+        let mut z = Zomboid::new(sample_items().into_iter().map(Ok::<_, io::Error>));
+        let table = z.describe_by(GroupField::Type).unwrap();
+        let shares: HashMap<&str, f64> = table
+            .as_data()
+            .iter()
+            .map(|v| (v.name.as_str(), v.value))
+            .collect();
+
+        assert_eq!(table.header().unwrap()[0], "TYPE");
+        assert!((shares["Tool"] - 14.0 / 514.0).abs() < 1e-9);
+        assert!((shares["Fasteners"] - 500.0 / 514.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn count() {
+        let mut z = Zomboid::new(sample_items().into_iter().map(Ok::<_, io::Error>));
+        z.set_skip(Some(1));
+        z.set_take(Some(3));
+        assert_eq!(z.count().unwrap(), 3);
+
+        let mut z = Zomboid::new(sample_items().into_iter().map(Ok::<_, io::Error>));
+        z.set_skip(Some(3));
+        z.set_take(Some(3));
+        assert_eq!(z.count().unwrap(), 2);
+
+        let items = vec![Err(io::Error::other("broken row"))];
+        let mut z = Zomboid::new(items.into_iter());
+        assert!(z.count().is_err());
+    }
+
+    #[test]
+    fn condition_entropy() {
+        let items = |conditions: &[&str]| {
+            conditions
+                .iter()
+                .map(|c| {
+                    let mut item = sample_items().remove(0);
+                    item.condition = (*c).into();
+                    Ok::<_, io::Error>(item)
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let mut z = Zomboid::new(items(&["Mint", "Mint"]).into_iter());
+        assert_eq!(z.condition_entropy().unwrap(), 0.0);
+
+        let mut z = Zomboid::new(items(&["Mint", "Good"]).into_iter());
+        assert!((z.condition_entropy().unwrap() - 1.0).abs() < 1e-9);
+
+        let mut z = Zomboid::new(items(&[]).into_iter());
+        assert_eq!(z.condition_entropy().unwrap(), 0.0);
+    }
+
+    #[test]
+    fn freshness() {
+        let data = "id,name,type,condition,amount,added\n\
+                    1,Hummer,Tool,Mint,10,2024-03-28\n\
+                    2,Nails,Fasteners,Good,20,2024-03-10\n\
+                    3,Garden saw,Tool,New,30,2023-12-31\n\
+                    4,Metal saw,Tool,New,40,\n";
+        let mut r = csv::Reader::from_reader(data.as_bytes());
+        let mut z = Zomboid::new(r.deserialize::<Item>());
+        let today = chrono::NaiveDate::from_ymd_opt(2024, 4, 1).unwrap();
+        let table = z.freshness_at(today).unwrap();
+
+        let shares: HashMap<&str, f64> = table
+            .as_data()
+            .iter()
+            .map(|v| (v.name.as_str(), v.value))
+            .collect();
+        assert_eq!(shares.len(), 4);
+        assert!((shares["< 7 days"] - 0.1).abs() < 1e-9);
+        assert!((shares["< 30 days"] - 0.2).abs() < 1e-9);
+        assert!((shares["older"] - 0.3).abs() < 1e-9);
+        assert!((shares["unknown"] - 0.4).abs() < 1e-9);
+    }
+
+    #[test]
+    fn builder_take_skip() {
+        let items = sample_items().into_iter().map(Ok::<_, io::Error>);
+        let mut z = Zomboid::new(items).skip(Some(1)).take(Some(2));
+        let table = z.stream().unwrap();
+
+        let conditions: Vec<&str> = table
+            .as_data()
+            .iter()
+            .map(|v| v.condition.as_str())
+            .collect();
+        assert_eq!(conditions, vec!["Good", "Mint"]);
+    }
+
+    #[test]
+    fn merge_by_id() {
+        let mut z = Zomboid::new(sample_items().into_iter().map(Ok::<_, io::Error>));
+        z.merge_by_id(true);
+        let table = z.stream().unwrap();
+        let items = table.as_data();
+
+        let ids: Vec<u32> = items.iter().map(|v| v.id).collect();
+        assert_eq!(ids, vec![1, 2, 3, 4]);
+        assert_eq!(items[1].amount, 500);
+        assert_eq!(items[1].name, "Nails");
+        assert_eq!(items[1].condition, "Mint,Good");
+    }
+
+    #[test]
+    fn merge_by_id_conditions_with_comma() {
+        let mut items = sample_items();
+        items[0].condition = "Mint, boxed".into();
+        items[1] = Item {
+            id: 1,
+            ..items[0].clone()
+        };
+        items[2] = Item {
+            id: 1,
+            condition: "Mint".into(),
+            ..items[0].clone()
+        };
+
+        let mut z = Zomboid::new(items.into_iter().map(Ok::<_, io::Error>));
+        z.merge_by_id(true);
+        let table = z.stream().unwrap();
+        let items = table.as_data();
+
+        assert_eq!(items[0].amount, 30);
+        assert_eq!(items[0].condition, "Mint,Mint, boxed");
+    }
+
+    #[test]
+    fn merge_by_id_saturates() {
+        let mut items = sample_items();
+        items[1].amount = u32::MAX - 1;
+
+        let mut z = Zomboid::new(items.into_iter().map(Ok::<_, io::Error>));
+        z.merge_by_id(true);
+        let table = z.stream().unwrap();
+
+        assert_eq!(table.as_data()[1].amount, u32::MAX);
     }
 }
@@ -0,0 +1,101 @@
+//! Merge module provides [`MergeSorted`] iterator that merges several sorted
+//! sources of items into a single sorted one.
+//!
+//! It's useful when data is split into several files, each of them sorted
+//! by the same key. Only one item per source is held in memory at a time.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use zombo::Zomboid;
+//!
+//! let mut r1 = csv::Reader::from_path("path/to/shelter1.csv").unwrap();
+//! let mut r2 = csv::Reader::from_path("path/to/shelter2.csv").unwrap();
+//!
+//! let mut z = Zomboid::merge_sorted(vec![r1.deserialize(), r2.deserialize()], |v| v.id);
+//! let table = z.stream().unwrap();
+//! ```
+use crate::model::Item;
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+
+/// Iterator performing k-way merge of sources sorted by the same key.
+///
+/// Items with equal keys are yielded in the order of their sources.
+/// Errors of the sources are yielded as soon as they are read.
+pub struct MergeSorted<S, F, K> {
+    sources: Vec<S>,
+    key: F,
+    heap: BinaryHeap<Reverse<Head<K>>>,
+    refill: Vec<usize>,
+}
+
+/// Next item of a source along with its key.
+struct Head<K> {
+    key: K,
+    source: usize,
+    item: Item,
+}
+
+impl<K: Ord> Ord for Head<K> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key
+            .cmp(&other.key)
+            .then(self.source.cmp(&other.source))
+    }
+}
+
+impl<K: Ord> PartialOrd for Head<K> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<K: Ord> PartialEq for Head<K> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<K: Ord> Eq for Head<K> {}
+
+impl<S, F, K> MergeSorted<S, F, K> {
+    pub fn new(sources: Vec<S>, key: F) -> Self {
+        Self {
+            refill: (0..sources.len()).rev().collect(),
+            heap: BinaryHeap::with_capacity(sources.len()),
+            sources,
+            key,
+        }
+    }
+}
+
+impl<S, F, K, E> Iterator for MergeSorted<S, F, K>
+where
+    S: Iterator<Item = Result<Item, E>>,
+    F: Fn(&Item) -> K,
+    K: Ord,
+{
+    type Item = Result<Item, E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(source) = self.refill.pop() {
+            match self.sources[source].next() {
+                Some(Ok(item)) => {
+                    let key = (self.key)(&item);
+                    self.heap.push(Reverse(Head { key, source, item }));
+                }
+                Some(Err(e)) => {
+                    // The source is polled again on the next call
+                    self.refill.push(source);
+                    return Some(Err(e));
+                }
+                None => {}
+            }
+        }
+
+        let Reverse(head) = self.heap.pop()?;
+        self.refill.push(head.source);
+        Some(Ok(head.item))
+    }
+}
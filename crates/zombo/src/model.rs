@@ -1,43 +1,961 @@
-use crate::table::RowDisplay;
+use crate::table::{format_cells, format_si, Alignment, RowDisplay, RowOptions, Table};
+use chrono::{Datelike, NaiveDate};
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Display;
 
 #[allow(dead_code)]
-#[derive(Debug, serde::Deserialize)]
+#[derive(Debug, Clone, Hash, PartialEq)]
 pub struct Item {
     pub id: u32,
     pub name: String,
 
-    // TODO: Add Enum for below's two fields
-    #[serde(rename = "type")]
+    // TODO: Add Enum for item type
     pub item_type: String,
-    pub condition: String,
+    pub condition: Condition,
     pub amount: u32,
+
+    /// Date when the item was added, if the source data has `added` column.
+    pub added: Option<NaiveDate>,
+
+    /// Columns of the source data other than the ones above, so no data is lost.
+    pub extra: BTreeMap<String, String>,
+}
+
+/// Serde layout of [`Item`] for text formats, e.g. CSV or JSON, where
+/// columns are matched by name and values of any type are read as text.
+#[derive(serde::Deserialize, serde::Serialize)]
+#[serde(remote = "Item")]
+struct TextItem {
+    id: u32,
+    #[serde(deserialize_with = "deserialize_text")]
+    name: String,
+    #[serde(rename = "type", deserialize_with = "deserialize_text")]
+    item_type: String,
+    condition: Condition,
+    #[serde(deserialize_with = "deserialize_amount")]
+    amount: u32,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_date",
+        skip_serializing_if = "Option::is_none"
+    )]
+    added: Option<NaiveDate>,
+    #[serde(flatten, deserialize_with = "deserialize_extra")]
+    extra: BTreeMap<String, String>,
+}
+
+/// Serde layout of [`Item`] for binary formats, e.g. bincode, that can't
+/// read values of unknown type, so nothing is flattened or inferred.
+#[derive(serde::Deserialize, serde::Serialize)]
+#[serde(remote = "Item")]
+struct BinaryItem {
+    id: u32,
+    name: String,
+    item_type: String,
+    condition: Condition,
+    amount: u32,
+    added: Option<NaiveDate>,
+    extra: BTreeMap<String, String>,
+}
+
+impl<'de> serde::Deserialize<'de> for Item {
+    fn deserialize<D: serde::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        match d.is_human_readable() {
+            true => TextItem::deserialize(d),
+            false => BinaryItem::deserialize(d),
+        }
+    }
+}
+
+impl serde::Serialize for Item {
+    fn serialize<S: serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        match s.is_human_readable() {
+            true => TextItem::serialize(self, s),
+            false => BinaryItem::serialize(self, s),
+        }
+    }
+}
+
+/// Condition of an [`Item`]. Known conditions are parsed case-insensitively,
+/// the rest are kept as is in [`Condition::Other`].
+///
+/// Conditions are ordered by severity, from [`Condition::Mint`] to
+/// [`Condition::Broken`], followed by [`Condition::Other`] ones by their text.
+#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Condition {
+    Mint,
+    New,
+    Good,
+    Worn,
+    Broken,
+    Other(String),
+}
+
+impl Condition {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Mint => "Mint",
+            Self::New => "New",
+            Self::Good => "Good",
+            Self::Worn => "Worn",
+            Self::Broken => "Broken",
+            Self::Other(v) => v,
+        }
+    }
+}
+
+impl From<&str> for Condition {
+    fn from(v: &str) -> Self {
+        match v.trim().to_lowercase().as_str() {
+            "mint" => Self::Mint,
+            "new" => Self::New,
+            "good" => Self::Good,
+            "worn" => Self::Worn,
+            "broken" => Self::Broken,
+            _ => Self::Other(v.to_string()),
+        }
+    }
+}
+
+impl From<String> for Condition {
+    fn from(v: String) -> Self {
+        Self::from(v.as_str())
+    }
+}
+
+impl Display for Condition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+impl PartialEq<&str> for Condition {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for Condition {
+    fn deserialize<D: serde::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        match d.is_human_readable() {
+            true => deserialize_text(d).map(Self::from),
+            false => <String as serde::Deserialize>::deserialize(d).map(Self::from),
+        }
+    }
+}
+
+impl serde::Serialize for Condition {
+    fn serialize<S: serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_str(self.as_str())
+    }
+}
+
+/// Deserializes any scalar value as a string.
+///
+/// Because of the flattened `extra` field, formats like CSV infer types of
+/// the values, so e.g. a name `123` would come as an integer.
+fn deserialize_text<'de, D: serde::Deserializer<'de>>(d: D) -> Result<String, D::Error> {
+    struct TextVisitor;
+
+    impl serde::de::Visitor<'_> for TextVisitor {
+        type Value = String;
+
+        fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            f.write_str("a string")
+        }
+
+        fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<String, E> {
+            Ok(v.to_string())
+        }
+
+        fn visit_u64<E: serde::de::Error>(self, v: u64) -> Result<String, E> {
+            Ok(v.to_string())
+        }
+
+        fn visit_i64<E: serde::de::Error>(self, v: i64) -> Result<String, E> {
+            Ok(v.to_string())
+        }
+
+        fn visit_f64<E: serde::de::Error>(self, v: f64) -> Result<String, E> {
+            Ok(v.to_string())
+        }
+
+        fn visit_bool<E: serde::de::Error>(self, v: bool) -> Result<String, E> {
+            Ok(v.to_string())
+        }
+
+        fn visit_unit<E: serde::de::Error>(self) -> Result<String, E> {
+            Ok(String::new())
+        }
+    }
+
+    d.deserialize_any(TextVisitor)
+}
+
+/// Deserializes a map of unknown columns with values of any scalar type as strings.
+fn deserialize_extra<'de, D: serde::Deserializer<'de>>(
+    d: D,
+) -> Result<BTreeMap<String, String>, D::Error> {
+    #[derive(serde::Deserialize)]
+    struct Text(#[serde(deserialize_with = "deserialize_text")] String);
+
+    let map = <BTreeMap<String, Text> as serde::Deserialize>::deserialize(d)?;
+    Ok(map.into_iter().map(|(k, v)| (k, v.0)).collect())
+}
+
+/// Deserializes an optional `YYYY-MM-DD` date. Empty value means no date.
+fn deserialize_date<'de, D: serde::Deserializer<'de>>(d: D) -> Result<Option<NaiveDate>, D::Error> {
+    struct DateVisitor;
+
+    impl<'de> serde::de::Visitor<'de> for DateVisitor {
+        type Value = Option<NaiveDate>;
+
+        fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            f.write_str("a date in YYYY-MM-DD format")
+        }
+
+        fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Self::Value, E> {
+            let v = v.trim();
+            if v.is_empty() {
+                return Ok(None);
+            }
+            NaiveDate::parse_from_str(v, "%Y-%m-%d")
+                .map(Some)
+                .map_err(|_| E::custom(format!("added '{}' is not a valid YYYY-MM-DD date", v)))
+        }
+
+        fn visit_unit<E: serde::de::Error>(self) -> Result<Self::Value, E> {
+            Ok(None)
+        }
+
+        fn visit_none<E: serde::de::Error>(self) -> Result<Self::Value, E> {
+            Ok(None)
+        }
+
+        fn visit_some<D: serde::Deserializer<'de>>(self, d: D) -> Result<Self::Value, D::Error> {
+            d.deserialize_any(self)
+        }
+    }
+
+    d.deserialize_any(DateVisitor)
 }
 
+/// Deserializes amount as a non-negative integer that fits into `u32`
+/// with a descriptive error otherwise.
+fn deserialize_amount<'de, D: serde::Deserializer<'de>>(d: D) -> Result<u32, D::Error> {
+    struct AmountVisitor;
+
+    fn invalid<E: serde::de::Error>(v: impl Display) -> E {
+        E::custom(format!(
+            "amount '{}' is not a valid non-negative integer",
+            v
+        ))
+    }
+
+    impl serde::de::Visitor<'_> for AmountVisitor {
+        type Value = u32;
+
+        fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            f.write_str("a valid non-negative integer amount")
+        }
+
+        fn visit_u64<E: serde::de::Error>(self, v: u64) -> Result<u32, E> {
+            u32::try_from(v).map_err(|_| invalid(v))
+        }
+
+        fn visit_i64<E: serde::de::Error>(self, v: i64) -> Result<u32, E> {
+            u32::try_from(v).map_err(|_| invalid(v))
+        }
+
+        fn visit_f64<E: serde::de::Error>(self, v: f64) -> Result<u32, E> {
+            Err(invalid(v))
+        }
+
+        fn visit_bool<E: serde::de::Error>(self, v: bool) -> Result<u32, E> {
+            Err(invalid(v))
+        }
+
+        fn visit_unit<E: serde::de::Error>(self) -> Result<u32, E> {
+            Err(invalid(""))
+        }
+
+        fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<u32, E> {
+            v.trim().parse().map_err(|_| invalid(v))
+        }
+    }
+
+    d.deserialize_any(AmountVisitor)
+}
+
+/// [`Item`] annotated with the cumulative amount up to and including it.
+#[derive(Debug)]
+pub struct RowWithTotal {
+    pub item: Item,
+    pub running: u64,
+}
+
+/// [`Item`] marked whether it satisfies a validation constraint.
+#[derive(Debug)]
+pub struct Annotated {
+    pub item: Item,
+    pub valid: bool,
+}
+
+/// [`Item`] repeated `count` times in a row.
 #[derive(Debug)]
+pub struct RunRow {
+    pub item: Item,
+    pub count: usize,
+}
+
+/// Row of a pivot table: total amount per condition for a single item type.
+#[derive(Debug)]
+pub struct PivotRow {
+    pub item_type: String,
+    pub totals: Vec<u32>,
+}
+
+/// Kind of change of an item id between two tables.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+/// Row of a table returned by [`Table::diff`].
+///
+/// `old` and `new` are the total amounts of the id in the old
+/// and new tables respectively.
+#[derive(Debug, PartialEq)]
+pub struct DiffRow {
+    pub id: u32,
+    pub kind: DiffKind,
+    pub old: Option<u64>,
+    pub new: Option<u64>,
+}
+
+#[derive(Debug, serde::Serialize)]
 pub struct Stat {
     pub name: String,
     pub value: f64,
 }
 
+/// [`Stat`] along with the cumulative share of it and all the preceding rows,
+/// built by [`crate::Zomboid::describe_pareto`].
+#[derive(Debug)]
+pub struct ParetoRow {
+    pub name: String,
+    pub value: f64,
+    pub cumulative: f64,
+}
+
+/// Named numeric value, e.g. a total or a mean amount. Unlike [`Stat`]
+/// the value is displayed as is, not as a percentage.
+#[derive(Debug)]
+pub struct Metric {
+    pub name: String,
+    pub value: f64,
+}
+
+impl Item {
+    /// Returns a stable hash of the item fields as a hex string.
+    ///
+    /// The hash is 64-bit FNV-1a, which doesn't depend on the platform
+    /// or Rust version, so rows can be compared across exports.
+    ///
+    /// The date and extra columns are hashed only if they are present,
+    /// so rows without them keep the same hash as before they were added.
+    pub fn row_hash(&self) -> String {
+        // Strings are prefixed with length so that moving chars
+        // between adjacent fields changes the hash.
+        let text = |hash, v: &str| {
+            let hash = fnv1a(hash, &(v.len() as u64).to_le_bytes());
+            fnv1a(hash, v.as_bytes())
+        };
+
+        let mut hash = fnv1a(FNV_OFFSET, &self.id.to_le_bytes());
+        for v in [&self.name, &self.item_type, self.condition.as_str()] {
+            hash = text(hash, v);
+        }
+        hash = fnv1a(hash, &self.amount.to_le_bytes());
+        if let Some(added) = self.added {
+            hash = fnv1a(hash, b"added");
+            hash = fnv1a(hash, &added.num_days_from_ce().to_le_bytes());
+        }
+        // Extra columns are kept sorted by name
+        for (k, v) in &self.extra {
+            hash = text(hash, k);
+            hash = text(hash, v);
+        }
+
+        format!("{:016x}", hash)
+    }
+}
+
+/// Initial value of 64-bit FNV-1a hash, see [`fnv1a`].
+pub(crate) const FNV_OFFSET: u64 = 0xcbf29ce484222325;
+
+/// Feeds `bytes` into 64-bit FNV-1a `hash` returning the updated one.
+pub(crate) fn fnv1a(hash: u64, bytes: &[u8]) -> u64 {
+    const PRIME: u64 = 0x100000001b3;
+    bytes
+        .iter()
+        .fold(hash, |hash, b| (hash ^ *b as u64).wrapping_mul(PRIME))
+}
+
+/// Footer of a table of items with `total` amount. Cells of the other
+/// columns are left blank.
+pub(crate) fn total_footer(total: u64) -> Vec<String> {
+    vec![
+        "TOTAL".to_string(),
+        String::new(),
+        String::new(),
+        String::new(),
+        total.to_string(),
+    ]
+}
+
+impl Table<Item> {
+    /// Compares the table against `other`, a newer snapshot of the same inventory.
+    ///
+    /// Items are keyed by id and amounts of the rows sharing the same id are summed.
+    /// Result lists ids that were added, removed or changed their total amount,
+    /// sorted by id.
+    pub fn diff(&self, other: &Table<Item>) -> Table<DiffRow> {
+        let totals = |table: &Table<Item>| {
+            let mut map = BTreeMap::<u32, u64>::new();
+            for v in table.as_data() {
+                *map.entry(v.id).or_insert(0) += v.amount as u64;
+            }
+            map
+        };
+        let old = totals(self);
+        let mut new = totals(other);
+
+        let mut rows = Vec::new();
+        for (id, amount) in old {
+            match new.remove(&id) {
+                None => rows.push(DiffRow {
+                    id,
+                    kind: DiffKind::Removed,
+                    old: Some(amount),
+                    new: None,
+                }),
+                Some(v) if v != amount => rows.push(DiffRow {
+                    id,
+                    kind: DiffKind::Changed,
+                    old: Some(amount),
+                    new: Some(v),
+                }),
+                Some(_) => {}
+            }
+        }
+        rows.extend(new.into_iter().map(|(id, amount)| DiffRow {
+            id,
+            kind: DiffKind::Added,
+            old: None,
+            new: Some(amount),
+        }));
+        rows.sort_by_key(|v| v.id);
+
+        Table::new(rows).with_header(vec!["ID", "CHANGE", "OLD", "NEW"])
+    }
+
+    /// Writes the items as CSV with a header row into `w`.
+    /// The `added` column is written only if some of the items have a date.
+    ///
+    /// Extra columns of the items, see [`Item::extra`], are written after the
+    /// known ones in alphabetical order. Items missing a column have it empty.
+    pub fn to_csv<W: std::io::Write>(&self, w: W) -> csv::Result<()> {
+        let extra: BTreeSet<&String> = self.as_data().iter().flat_map(|v| v.extra.keys()).collect();
+
+        let dated = self.as_data().iter().any(|v| v.added.is_some());
+
+        let mut writer = csv::Writer::from_writer(w);
+        let header = ["id", "name", "type", "condition", "amount"];
+        let added = dated.then_some("added");
+        writer.write_record(
+            header
+                .into_iter()
+                .chain(added)
+                .chain(extra.iter().map(|v| v.as_str())),
+        )?;
+
+        for v in self.as_data() {
+            let mut record = vec![
+                v.id.to_string(),
+                v.name.clone(),
+                v.item_type.clone(),
+                v.condition.to_string(),
+                v.amount.to_string(),
+            ];
+            if dated {
+                record.push(v.added.map(|v| v.to_string()).unwrap_or_default());
+            }
+            record.extend(
+                extra
+                    .iter()
+                    .map(|k| v.extra.get(*k).cloned().unwrap_or_default()),
+            );
+            writer.write_record(record)?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Adds a footer with the total amount of the items. Cells of the other
+    /// columns are left blank.
+    pub fn with_auto_footer(self) -> Self {
+        let total: u64 = self.as_data().iter().map(|v| v.amount as u64).sum();
+        self.with_footer(total_footer(total))
+    }
+
+    /// Sorts items by name. With `natural` numbers embedded into names are
+    /// compared by value, so `Box 2` goes before `Box 10`, see [`natural_cmp`].
+    /// The sort is stable.
+    pub fn sort_by_name(&mut self, natural: bool) {
+        self.sort_by(|a, b| match natural {
+            true => natural_cmp(&a.name, &b.name),
+            false => a.name.cmp(&b.name),
+        });
+    }
+}
+
+/// Compares strings so that runs of digits are compared as numbers
+/// and the rest of characters as is, e.g. `Saw 2` < `Saw 10`.
+pub fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let (mut a, mut b) = (a, b);
+    loop {
+        let (Some(ca), Some(cb)) = (a.chars().next(), b.chars().next()) else {
+            return a.len().cmp(&b.len());
+        };
+
+        let ord = match (ca.is_ascii_digit(), cb.is_ascii_digit()) {
+            (true, true) => {
+                let (na, rest_a) = split_digits(a);
+                let (nb, rest_b) = split_digits(b);
+                a = rest_a;
+                b = rest_b;
+                // Leading zeros don't change the value
+                let (na, nb) = (na.trim_start_matches('0'), nb.trim_start_matches('0'));
+                na.len().cmp(&nb.len()).then_with(|| na.cmp(nb))
+            }
+            _ => {
+                a = &a[ca.len_utf8()..];
+                b = &b[cb.len_utf8()..];
+                ca.cmp(&cb)
+            }
+        };
+        if ord != Ordering::Equal {
+            return ord;
+        }
+    }
+}
+
+/// Splits `s` into the leading run of digits and the rest.
+fn split_digits(s: &str) -> (&str, &str) {
+    let end = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    s.split_at(end)
+}
+
+impl Table<Stat> {
+    /// Sorts stats by value, ties are ordered by name alphabetically.
+    pub fn sort_by_value(&mut self, descending: bool) {
+        self.sort_by(|a, b| {
+            let by_value = a.value.total_cmp(&b.value);
+            match descending {
+                true => by_value.reverse(),
+                false => by_value,
+            }
+            .then_with(|| a.name.cmp(&b.name))
+        });
+    }
+}
+
 impl RowDisplay for Item {
     fn to_row(&self, table_width: usize) -> String {
-        let width = table_width / 5 - 3;
+        self.to_row_with(table_width, &RowOptions::default())
+    }
+
+    fn to_row_with(&self, table_width: usize, options: &RowOptions) -> String {
+        let mut cells = self.cells();
+        if options.si_amounts {
+            cells[4] = format_si(self.amount as u64);
+        }
+        let alignments = match options.alignments.is_empty() {
+            true => self.alignments(),
+            false => options.alignments.clone(),
+        };
+        format_cells(&cells, table_width, &alignments)
+    }
+
+    /// Numbers, i.e. id and amount, are right aligned.
+    fn alignments(&self) -> Vec<Alignment> {
+        vec![
+            Alignment::Right,
+            Alignment::Center,
+            Alignment::Center,
+            Alignment::Center,
+            Alignment::Right,
+        ]
+    }
+
+    fn cells(&self) -> Vec<String> {
+        vec![
+            self.id.to_string(),
+            self.name.clone(),
+            self.item_type.clone(),
+            self.condition.to_string(),
+            self.amount.to_string(),
+        ]
+    }
+}
+
+impl RowDisplay for RowWithTotal {
+    fn to_row(&self, table_width: usize) -> String {
+        format_cells(&self.cells(), table_width, &[])
+    }
+
+    fn cells(&self) -> Vec<String> {
+        let mut cells = self.item.cells();
+        cells.push(self.running.to_string());
+        cells
+    }
+}
+
+impl RowDisplay for Annotated {
+    fn to_row(&self, table_width: usize) -> String {
+        format_cells(&self.cells(), table_width, &[])
+    }
 
-        format!(
-            "│ {:^width$} │ {:^width$} │ {:^width$} │ {:^width$} │ {:^width$}│",
-            self.id, self.name, self.item_type, self.condition, self.amount
-        )
+    fn cells(&self) -> Vec<String> {
+        let mut cells = self.item.cells();
+        cells.push(if self.valid { "✓" } else { "✗" }.to_string());
+        cells
+    }
+}
+
+impl RowDisplay for RunRow {
+    fn to_row(&self, table_width: usize) -> String {
+        format_cells(&self.cells(), table_width, &[])
+    }
+
+    fn cells(&self) -> Vec<String> {
+        let mut cells = self.item.cells();
+        cells.push(self.count.to_string());
+        cells
+    }
+}
+
+impl RowDisplay for PivotRow {
+    fn to_row(&self, table_width: usize) -> String {
+        format_cells(&self.cells(), table_width, &[])
+    }
+
+    fn cells(&self) -> Vec<String> {
+        let mut cells = vec![self.item_type.clone()];
+        cells.extend(self.totals.iter().map(|v| v.to_string()));
+        cells
+    }
+}
+
+impl Display for DiffKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Self::Added => "Added",
+            Self::Removed => "Removed",
+            Self::Changed => "Changed",
+        };
+        f.pad(s)
+    }
+}
+
+impl RowDisplay for DiffRow {
+    fn to_row(&self, table_width: usize) -> String {
+        format_cells(&self.cells(), table_width, &[])
+    }
+
+    fn cells(&self) -> Vec<String> {
+        let amount = |v: Option<u64>| v.map_or("-".to_string(), |v| v.to_string());
+        vec![
+            self.id.to_string(),
+            self.kind.to_string(),
+            amount(self.old),
+            amount(self.new),
+        ]
     }
 }
 
 impl RowDisplay for Stat {
     fn to_row(&self, table_width: usize) -> String {
-        let width = table_width / 2 - 3;
+        format_cells(&self.cells(), table_width, &[])
+    }
+
+    fn cells(&self) -> Vec<String> {
+        vec![self.name.clone(), format!("{:04.1}%", self.value * 100.0)]
+    }
+}
+
+impl RowDisplay for ParetoRow {
+    fn to_row(&self, table_width: usize) -> String {
+        format_cells(&self.cells(), table_width, &[])
+    }
+
+    fn cells(&self) -> Vec<String> {
+        vec![
+            self.name.clone(),
+            format!("{:04.1}%", self.value * 100.0),
+            format!("{:04.1}%", self.cumulative * 100.0),
+        ]
+    }
+}
+
+impl RowDisplay for Metric {
+    fn to_row(&self, table_width: usize) -> String {
+        format_cells(&self.cells(), table_width, &[])
+    }
+
+    fn cells(&self) -> Vec<String> {
+        // Whole numbers are displayed without fraction
+        let value = match self.value.fract() == 0.0 {
+            true => format!("{:.0}", self.value),
+            false => format!("{:.2}", self.value),
+        };
+        vec![self.name.clone(), value]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(id: u32, amount: u32) -> Item {
+        Item {
+            id,
+            name: "Nails".into(),
+            item_type: "Fasteners".into(),
+            condition: "Good".into(),
+            amount,
+            added: None,
+            extra: Default::default(),
+        }
+    }
+
+    #[test]
+    fn table_diff() {
+        let old = Table::new(vec![item(1, 10), item(2, 400), item(2, 100)]);
+        let new = Table::new(vec![item(1, 10), item(2, 450), item(3, 5)]);
+
+        let diff = old.diff(&new);
+
+        assert_eq!(
+            *diff.as_data(),
+            vec![
+                DiffRow {
+                    id: 2,
+                    kind: DiffKind::Changed,
+                    old: Some(500),
+                    new: Some(450),
+                },
+                DiffRow {
+                    id: 3,
+                    kind: DiffKind::Added,
+                    old: None,
+                    new: Some(5),
+                },
+            ]
+        );
+        println!("{}", diff);
+    }
+
+    #[test]
+    fn item_row_hash() {
+        let hash = item(1, 10).row_hash();
+
+        assert_eq!(hash.len(), 16);
+        assert_eq!(hash, item(1, 10).row_hash());
+        assert_ne!(hash, item(1, 11).row_hash());
+
+        let mut added = item(1, 10);
+        added.added = NaiveDate::from_ymd_opt(2024, 5, 1);
+        assert_ne!(hash, added.row_hash());
+
+        let mut extra = item(1, 10);
+        extra.extra.insert("shelf".into(), "B2".into());
+        assert_ne!(hash, extra.row_hash());
+        let mut moved = item(1, 10);
+        moved.extra.insert("shelfB".into(), "2".into());
+        assert_ne!(extra.row_hash(), moved.row_hash());
+    }
+
+    #[test]
+    fn item_si_amounts() {
+        let table = Table::new(vec![item(1, 1500), item(2, 2)]).with_si_amounts(true);
+        let table_string = format!("{}", table);
+        let rows: Vec<&str> = table_string.split("\n").collect();
+
+        let amount = |row: &str| row.rsplit('│').nth(1).unwrap().trim().to_string();
+        assert_eq!(amount(rows[1]), "1.5k");
+        assert_eq!(amount(rows[2]), "2");
+
+        assert_eq!(format_si(12_000), "12k");
+        assert_eq!(format_si(1_500_000), "1.5M");
+        assert_eq!(format_si(999_960), "1M");
+    }
+
+    #[test]
+    fn item_invalid_amount() {
+        let data = "id,name,type,condition,amount\n\
+                    1,Hummer,Tool,Mint,10\n\
+                    2,Nails,Fasteners,Good,abc\n\
+                    3,Garden saw,Tool,New,-2\n\
+                    4,Metal saw,Tool,New,\n";
+        let mut r = csv::Reader::from_reader(data.as_bytes());
+        let errors: Vec<String> = r
+            .deserialize::<Item>()
+            .filter_map(|v| v.map_err(crate::Error::from).err())
+            .map(|e| e.to_string())
+            .collect();
+
+        assert_eq!(
+            errors,
+            vec![
+                "row 2: amount 'abc' is not a valid non-negative integer",
+                "row 3: amount '-2' is not a valid non-negative integer",
+                "row 4: amount '' is not a valid non-negative integer",
+            ]
+        );
+    }
+
+    #[test]
+    fn stat_sort_by_value() {
+        let stat = |name: &str, value: f64| Stat {
+            name: name.into(),
+            value,
+        };
+        let mut table = Table::new(vec![
+            stat("New", 0.2),
+            stat("Mint", 0.4),
+            stat("Good", 0.2),
+            stat("Worn", 0.2),
+        ]);
+
+        table.sort_by_value(true);
+        let names: Vec<&str> = table.as_data().iter().map(|v| v.name.as_str()).collect();
+
+        assert_eq!(names, vec!["Mint", "Good", "New", "Worn"]);
+    }
+
+    #[test]
+    fn stat_narrow_width() {
+        let stat = Stat {
+            name: "Mint".into(),
+            value: 0.5,
+        };
+        let table = Table::new(vec![stat]).with_width(3);
+        assert!(table.to_string().contains("Mint"));
+    }
+
+    #[test]
+    fn item_sort_by_name_natural() {
+        let named = |name: &str| Item {
+            name: name.into(),
+            ..item(1, 1)
+        };
+        let mut table = Table::new(vec![named("Box 2"), named("Box 10"), named("Box 1")]);
+
+        table.sort_by_name(false);
+        let names: Vec<&str> = table.as_data().iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(names, vec!["Box 1", "Box 10", "Box 2"]);
+
+        table.sort_by_name(true);
+        let names: Vec<&str> = table.as_data().iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(names, vec!["Box 1", "Box 2", "Box 10"]);
+    }
+
+    #[test]
+    fn item_to_csv_round_trip() {
+        let data = "id,name,type,condition,amount\n\
+                    1,Hummer,Tool,Mint,10\n\
+                    2,Nails,Fasteners,Good,400\n\
+                    2,Nails,Fasteners,Mint,100\n\
+                    3,Garden saw,Tool,New,2\n\
+                    4,Metal saw,Tool,New,2\n";
+        let read = |data: &[u8]| -> Vec<Item> {
+            let mut r = csv::Reader::from_reader(data);
+            r.deserialize().map(|v| v.unwrap()).collect()
+        };
+
+        let mut z = crate::Zomboid::new(
+            read(data.as_bytes())
+                .into_iter()
+                .map(Ok::<_, std::io::Error>),
+        );
+        let table = z.stream().unwrap();
+
+        let mut out = Vec::new();
+        table.to_csv(&mut out).unwrap();
+
+        assert_eq!(String::from_utf8(out.clone()).unwrap(), data);
+        assert_eq!(read(&out), *table.as_data());
+    }
+
+    #[test]
+    fn item_numbers_right_aligned() {
+        let row = item(7, 400).to_row(60);
+        let cells: Vec<&str> = row.split('│').collect();
+
+        // Single space of padding separates the digits from the border
+        assert!(cells[1].ends_with("7 ") && cells[1].starts_with("   "));
+        assert!(cells[5].ends_with("400 ") && cells[5].starts_with("   "));
+        assert_eq!(cells[2].trim_start().len(), cells[2].trim_end().len());
+    }
+
+    #[test]
+    fn item_auto_footer() {
+        let table = Table::new(vec![item(1, 10), item(2, 400), item(3, 2)])
+            .with_header(vec!["ID", "NAME", "TYPE", "CONDITION", "AMOUNT"])
+            .with_auto_footer();
+        let table_string = format!("{}", table);
+        let rows: Vec<&str> = table_string.split("\n").collect();
+
+        // top, header, mid, 3 items, mid, footer, bottom
+        assert_eq!(rows.len(), 9);
+        assert!(rows[6].starts_with('├'));
+        let amount = rows[7].rsplit('│').nth(1).unwrap().trim();
+        assert_eq!(amount, "412");
+    }
+
+    #[test]
+    fn item_extra_columns() {
+        let data = "id,name,type,condition,amount,notes,shelf\n\
+                    1,Hummer,Tool,Mint,10,Slightly used,5\n\
+                    2,123,Fasteners,Good,400,,B\n";
+        let mut r = csv::Reader::from_reader(data.as_bytes());
+        let items: Vec<Item> = r.deserialize().map(|v| v.unwrap()).collect();
+
+        assert_eq!((items[0].id, items[0].amount), (1, 10));
+        assert_eq!(items[0].extra["notes"], "Slightly used");
+        assert_eq!(items[0].extra["shelf"], "5");
+        assert_eq!(items[1].name, "123");
+        assert_eq!(items[1].extra["notes"], "");
+        assert!(!items[1].extra.contains_key("amount"));
+    }
+
+    #[test]
+    fn item_condition() {
+        let data = "id,name,type,condition,amount\n\
+                    1,Hummer,Tool,Mint,10\n\
+                    2,Nails,Fasteners,broken,400\n\
+                    3,Saw,Tool,Mnt,2\n";
+        let mut r = csv::Reader::from_reader(data.as_bytes());
+        let items: Vec<Item> = r.deserialize().map(|v| v.unwrap()).collect();
 
-        format!(
-            "│ {:^width$} │ {:^width$}│",
-            self.name,
-            format!("{:04.1}%", self.value * 100.0)
-        )
+        assert_eq!(items[0].condition, Condition::Mint);
+        assert_eq!(items[1].condition, Condition::Broken);
+        assert_eq!(items[2].condition, Condition::Other("Mnt".into()));
+        assert_eq!(items[2].condition.to_string(), "Mnt");
     }
 }
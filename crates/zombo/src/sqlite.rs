@@ -0,0 +1,96 @@
+//! Reading items from SQLite database, enabled by `sqlite` feature.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use rusqlite::Connection;
+//! use zombo::Zomboid;
+//!
+//! let conn = Connection::open("path/to/shelter.db").unwrap();
+//! let mut stmt = conn.prepare("SELECT * FROM inventory").unwrap();
+//! let mut z = Zomboid::new(zombo::sqlite::query(&mut stmt));
+//! let table = z.stream().unwrap();
+//! ```
+use crate::model::Item;
+use rusqlite::{Row, Statement};
+
+/// Runs prepared `stmt` and maps each row to an [`Item`] by column names,
+/// i.e. the query has to select `id`, `name`, `type`, `condition` and `amount`.
+///
+/// Rows are read lazily, one per iteration, so the iterator borrows the
+/// statement. Failure to run the query is yielded as the only error.
+pub fn query<'s>(
+    stmt: &'s mut Statement<'_>,
+) -> impl Iterator<Item = Result<Item, rusqlite::Error>> + 's {
+    let (rows, error) = match stmt.query_map([], to_item) {
+        Ok(rows) => (Some(rows), None),
+        Err(e) => (None, Some(Err(e))),
+    };
+    rows.into_iter().flatten().chain(error)
+}
+
+fn to_item(row: &Row) -> Result<Item, rusqlite::Error> {
+    Ok(Item {
+        id: row.get("id")?,
+        name: row.get("name")?,
+        item_type: row.get("type")?,
+        condition: row.get::<_, String>("condition")?.into(),
+        amount: row.get("amount")?,
+        added: None,
+        extra: Default::default(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Zomboid;
+    use rusqlite::Connection;
+
+    #[test]
+    fn query() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE inventory (id INTEGER, name TEXT, type TEXT, condition TEXT, amount INTEGER);
+             INSERT INTO inventory VALUES (1, 'Hummer', 'Tool', 'Mint', 10);
+             INSERT INTO inventory VALUES (2, 'Nails', 'Fasteners', 'Good', 400);
+             INSERT INTO inventory VALUES (3, 'Garden saw', 'Tool', 'New', 2);",
+        )
+        .unwrap();
+
+        let mut stmt = conn
+            .prepare("SELECT * FROM inventory WHERE type = 'Tool'")
+            .unwrap();
+        let table = Zomboid::new(super::query(&mut stmt)).stream().unwrap();
+
+        let names: Vec<&str> = table.as_data().iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(names, vec!["Hummer", "Garden saw"]);
+
+        // Query with unbound parameter fails to run
+        let mut stmt = conn
+            .prepare("SELECT * FROM inventory WHERE id = ?")
+            .unwrap();
+        let mut items = super::query(&mut stmt);
+        assert!(items.next().unwrap().is_err());
+        assert!(items.next().is_none());
+    }
+
+    #[test]
+    fn query_lazily() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE inventory (id INTEGER, name TEXT, type TEXT, condition TEXT, amount INTEGER);
+             INSERT INTO inventory VALUES (1, 'Hummer', 'Tool', 'Mint', 10);
+             INSERT INTO inventory VALUES (2, 'Nails', 'Fasteners', 'Good', 'many');",
+        )
+        .unwrap();
+
+        // The broken second row isn't read when only the first one is taken
+        let mut stmt = conn.prepare("SELECT * FROM inventory").unwrap();
+        let mut z = Zomboid::new(super::query(&mut stmt));
+        z.set_take(Some(1));
+        assert_eq!(z.stream().unwrap().as_data().len(), 1);
+
+        let mut stmt = conn.prepare("SELECT * FROM inventory").unwrap();
+        assert!(Zomboid::new(super::query(&mut stmt)).stream().is_err());
+    }
+}
@@ -20,6 +20,7 @@
 //! and further usage of [`Table`] struct could be
 //!
 //! ```rust
+//! use zombo::table::cell_widths;
 //! use zombo::table::RowDisplay;
 //! use zombo::table::Table;
 //!
@@ -32,12 +33,16 @@
 //!     fn to_row(&self, table_width: usize) -> String {
 //!         // table_width is the width of table in characters.
 //!         //
-//!         // Divide on 2 because Row has two fields and we
+//!         // Split it on 2 because Row has two fields and we
 //!         // want to give them both cells an equal width.
 //!         //
-//!         // Minus 3 because we add 3 additional chars to each cell
-//!         let width = table_width / 2 - 3;
-//!         format!("│ {:^width$} │ {:^width$}│", self.id, self.name)
+//!         // Last cell takes the remainder of the division.
+//!         let (width, last) = cell_widths(table_width, 2);
+//!         format!("│ {:^width$} │ {:^last$} │", self.id, self.name)
+//!     }
+//!
+//!     fn cells(&self) -> Vec<String> {
+//!         vec![self.id.to_string(), self.name.clone()]
 //!     }
 //! }
 //!
@@ -54,9 +59,19 @@
 //! let data = table.as_data();
 //! ```
 //!
-//! Currently [`Table`] only supports header of static strings. However, this is a subject
-//! to change later.
-use std::fmt::Display;
+//! With `derive` feature enabled, [`RowDisplay`] can be derived instead, see
+//! `zombo-derive` crate. Each field becomes a centered cell.
+//!
+//! Header labels may be built at runtime, e.g. from column names of a CSV file,
+//! since [`Table::with_header`] accepts anything convertible into [`String`].
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Write};
+use std::ops::Range;
+use std::sync::OnceLock;
+use unicode_width::UnicodeWidthStr;
+#[cfg(feature = "derive")]
+pub use zombo_derive::RowDisplay;
 
 /// A trait to implement if you want a type to be formatted
 /// as a row of a table.
@@ -66,52 +81,597 @@ pub trait RowDisplay {
     /// # Arguments
     ///
     /// * `table_width` is a table width in characters. This argument may be useful to
-    ///                 calculate the size of a cell of a row.
+    ///   calculate the size of a cell of a row.
     fn to_row(&self, table_width: usize) -> String;
+
+    /// Returns values of the row cells as plain strings, without any padding
+    /// or borders. It's used by renderers other than the box-drawn one.
+    ///
+    /// This method is required since `0.2.0`: implementors written against
+    /// `0.1` have to add it next to [`RowDisplay::to_row`].
+    fn cells(&self) -> Vec<String>;
+
+    /// Returns default alignment of each column, e.g. to right-align numbers.
+    /// It's used when the table has no alignments set by [`Table::with_alignments`].
+    ///
+    /// Columns without alignment are centered, which is the default for all of them.
+    fn alignments(&self) -> Vec<Alignment> {
+        Vec::new()
+    }
+
+    /// Same as [`RowDisplay::to_row`] but with rendering options of the table.
+    ///
+    /// Default implementation supports only column alignments by formatting
+    /// [`RowDisplay::cells`] with [`format_cells`]. Override it if the row
+    /// supports any other option.
+    fn to_row_with(&self, table_width: usize, options: &RowOptions) -> String {
+        match options.alignments.is_empty() {
+            true => self.to_row(table_width),
+            false => format_cells(&self.cells(), table_width, &options.alignments),
+        }
+    }
+
+    /// Returns `true` if all the cells of the row are blank.
+    fn is_empty(&self) -> bool {
+        self.cells().iter().all(|v| v.trim().is_empty())
+    }
+}
+
+/// Rendering options of a [`Table`] that are passed down to its rows
+/// by [`RowDisplay::to_row_with`].
+#[derive(Debug, Default, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[non_exhaustive]
+pub struct RowOptions {
+    /// Format numeric cells with SI suffixes, see [`format_si`]
+    pub si_amounts: bool,
+    /// Alignment of each column. Columns without alignment are centered.
+    pub alignments: Vec<Alignment>,
+}
+
+/// Horizontal alignment of a cell content.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum Alignment {
+    Left,
+    #[default]
+    Center,
+    Right,
+    /// Numbers are right aligned so that their decimal points line up,
+    /// e.g. `2`, `10.5` and `400.25`. The rest of cells are right aligned.
+    ///
+    /// It's applied by [`Table`] that knows all the cells of the column.
+    /// A single row formatted by [`format_cells`] is just right aligned.
+    Decimal,
+}
+
+/// Formats `cells` into a row of `table_width` chars, see [`cell_widths`].
+///
+/// Cell at index `i` is aligned by `alignments[i]`, or centered if there is none.
+pub fn format_cells(cells: &[String], table_width: usize, alignments: &[Alignment]) -> String {
+    format_sized(cells, &even_widths(table_width, cells.len()), alignments)
+}
+
+/// Same as [`format_cells`] but cell at index `i` is `widths[i]` chars wide.
+fn format_sized(cells: &[String], widths: &[usize], alignments: &[Alignment]) -> String {
+    let mut s = String::new();
+    for (i, v) in cells.iter().enumerate() {
+        let width = widths.get(i).copied().unwrap_or(1);
+        let cell = pad(v, width, alignments.get(i).copied().unwrap_or_default());
+        s.push_str(&format!("│ {} ", cell));
+    }
+    s.push('│');
+    s
+}
+
+/// Pads `v` with spaces up to `width` terminal columns according to `alignment`.
+///
+/// Unlike `{:^width$}`, which counts chars, it measures display width, so wide
+/// glyphs, e.g. CJK or emoji, take two columns and combining marks take none.
+pub(crate) fn pad(v: &str, width: usize, alignment: Alignment) -> String {
+    let fill = width.saturating_sub(v.width());
+    let (left, right) = match alignment {
+        Alignment::Left => (0, fill),
+        Alignment::Center => (fill / 2, fill - fill / 2),
+        Alignment::Right | Alignment::Decimal => (fill, 0),
+    };
+    format!("{}{}{}", " ".repeat(left), v, " ".repeat(right))
+}
+
+/// Formats a number with SI suffix and one decimal, e.g. `1500` as `1.5k`
+/// and `12000` as `12k`. Numbers below a thousand are kept as is.
+pub fn format_si(v: u64) -> String {
+    const UNITS: [&str; 5] = ["", "k", "M", "G", "T"];
+
+    let mut value = v as f64;
+    let mut unit = 0;
+    while unit + 1 < UNITS.len() && (value * 10.0).round() / 10.0 >= 1000.0 {
+        value /= 1000.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        return v.to_string();
+    }
+    let s = format!("{:.1}", value);
+    format!("{}{}", s.strip_suffix(".0").unwrap_or(&s), UNITS[unit])
+}
+
+/// Splits `table_width` into widths of the cells content for a row of `columns` cells.
+///
+/// Returns width of each cell but the last one and width of the last cell, which
+/// takes the remainder of the division. Each cell is padded with a space on both
+/// sides and surrounded by `│`, so a row formatted as
+/// `│ {:^width$} │ ... │ {:^last$} │` is exactly `table_width` chars long.
+///
+/// Cells are at least 1 char wide, so a row of a table too narrow to fit
+/// all the columns is longer than `table_width`.
+pub fn cell_widths(table_width: usize, columns: usize) -> (usize, usize) {
+    let columns = columns.max(1);
+    let width = (table_width / columns).saturating_sub(3).max(1);
+    let last = table_width
+        .saturating_sub((columns - 1) * (width + 3) + 4)
+        .max(1);
+    (width, last)
+}
+
+/// Same as [`cell_widths`] but returns width of each of `columns` cells.
+fn even_widths(table_width: usize, columns: usize) -> Vec<usize> {
+    let (width, last) = cell_widths(table_width, columns);
+    (0..columns)
+        .map(|i| if i + 1 == columns { last } else { width })
+        .collect()
+}
+
+/// Draws a horizontal border of `width` chars.
+fn sep(width: usize, left: char, fill: char, right: char) -> String {
+    let line = fill.to_string().repeat(width.saturating_sub(2));
+    format!("{left}{line}{right}")
+}
+
+/// Preset of glyphs used to draw the table borders and dividers.
+///
+/// Rows are expected to use `│` as a divider, it's replaced with the vertical
+/// glyph of the chosen style on rendering.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum BorderStyle {
+    /// `┌─┐` single line borders
+    #[default]
+    Single,
+    /// `╭─╮` single line borders with rounded corners
+    Rounded,
+    /// `╔═╗` double line borders
+    Double,
+    /// `┏━┓` heavy line borders
+    Heavy,
+    /// Horizontal separators only, without corners and vertical dividers
+    Minimal,
+    /// No borders, columns are separated with spaces and the header
+    /// is underlined with dashes, e.g.
+    ///
+    /// ```text
+    ///  ID    NAME
+    ///  --    ----
+    ///  1     Hummer
+    /// ```
+    Markdownish,
+}
+
+struct Glyphs {
+    top_left: char,
+    top_right: char,
+    middle_left: char,
+    middle_right: char,
+    bottom_left: char,
+    bottom_right: char,
+    horizontal: char,
+    vertical: char,
+}
+
+impl BorderStyle {
+    fn glyphs(&self) -> Glyphs {
+        let [top_left, top_right, middle_left, middle_right, bottom_left, bottom_right, horizontal, vertical] =
+            match self {
+                Self::Single => ['┌', '┐', '├', '┤', '└', '┘', '─', '│'],
+                Self::Rounded => ['╭', '╮', '├', '┤', '╰', '╯', '─', '│'],
+                Self::Double => ['╔', '╗', '╠', '╣', '╚', '╝', '═', '║'],
+                Self::Heavy => ['┏', '┓', '┣', '┫', '┗', '┛', '━', '┃'],
+                Self::Minimal => [' ', ' ', ' ', ' ', ' ', ' ', '─', ' '],
+                Self::Markdownish => [' ', ' ', ' ', ' ', ' ', ' ', '-', ' '],
+            };
+
+        Glyphs {
+            top_left,
+            top_right,
+            middle_left,
+            middle_right,
+            bottom_left,
+            bottom_right,
+            horizontal,
+            vertical,
+        }
+    }
+}
+
+/// Defines which rows are numbered when row numbers are enabled
+/// with [`Table::with_row_numbers`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum NumberingMode {
+    /// Every row is numbered
+    #[default]
+    All,
+    /// Empty rows, see [`RowDisplay::is_empty`], are left without number
+    /// and don't advance the counter.
+    NonEmpty,
 }
 
 /// Table represents a container for data to be formatted as a table.
 /// Optionally, you may set a header to the table and width in characters.
-/// Currenty, table header accepts only a Vec of 'static strings.
-#[derive(Debug)]
+///
+/// Table can be serialized along with its rendering options, e.g. to cache
+/// a report between runs. The render cache isn't serialized.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
 pub struct Table<T> {
-    header: Option<Vec<&'static str>>,
+    header: Option<Vec<String>>,
+    footer: Option<Vec<String>>,
     width: usize,
+    style: BorderStyle,
+    numbering: Option<NumberingMode>,
+    header_repeat: Option<usize>,
+    indent: usize,
+    hidden: Vec<usize>,
+    header_groups: Vec<(String, usize)>,
+    zebra: bool,
+    empty_placeholder: Option<String>,
+    empty_message: String,
+    autosize: bool,
+    frame: Option<(String, Option<String>)>,
+    options: RowOptions,
     data: Vec<T>,
+    #[serde(skip)]
+    rendered: OnceLock<String>,
 }
 
 impl<T> Table<T> {
     pub fn new(data: Vec<T>) -> Self {
         Self {
             header: None,
+            footer: None,
             data,
             width: 100,
+            style: BorderStyle::default(),
+            numbering: None,
+            header_repeat: None,
+            indent: 0,
+            hidden: Vec::new(),
+            header_groups: Vec::new(),
+            zebra: false,
+            empty_placeholder: None,
+            empty_message: "No data".into(),
+            autosize: false,
+            frame: None,
+            options: RowOptions::default(),
+            rendered: OnceLock::new(),
         }
     }
 
-    pub fn with_header(mut self, header: Vec<&'static str>) -> Self {
-        self.header = Some(header);
+    /// Sets labels of the header, e.g. `vec!["ID", "NAME"]` or
+    /// column names read at runtime.
+    pub fn with_header(mut self, header: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.header = Some(header.into_iter().map(Into::into).collect());
+        self.rendered.take();
+        self
+    }
+
+    /// Sets a footer row that is rendered below the data, separated from it
+    /// by a border, e.g. to show totals of the columns.
+    pub fn with_footer(mut self, footer: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.footer = Some(footer.into_iter().map(Into::into).collect());
+        self.rendered.take();
         self
     }
 
+    /// Sets width of the table in characters, the columns share it evenly.
+    /// It turns off [`Table::with_autosize`].
     pub fn with_width(mut self, width: usize) -> Self {
         self.width = width;
+        self.autosize = false;
+        self.rendered.take();
+        self
+    }
+
+    pub fn with_style(mut self, style: BorderStyle) -> Self {
+        self.style = style;
+        self.rendered.take();
+        self
+    }
+
+    /// Format numeric cells of the rows with SI suffixes, e.g. `1.5k`.
+    /// Rows have to support it in [`RowDisplay::to_row_with`].
+    pub fn with_si_amounts(mut self, v: bool) -> Self {
+        self.options.si_amounts = v;
+        self.rendered.take();
         self
     }
 
-    fn top_sep(&self) -> String {
-        let width = self.width - 2;
-        format!("┌{:─^width$}┐", "")
+    /// Sets alignment of each column of the rows.
+    /// Rows have to support it in [`RowDisplay::to_row_with`].
+    pub fn with_alignments(mut self, alignments: Vec<Alignment>) -> Self {
+        self.options.alignments = alignments;
+        self.rendered.take();
+        self
+    }
+
+    /// Prepends a `#` column with row numbers to the table. Total width
+    /// of the table stays the same.
+    pub fn with_row_numbers(mut self, mode: NumberingMode) -> Self {
+        self.numbering = Some(mode);
+        self.rendered.take();
+        self
+    }
+
+    /// Reprints the header every `rows` data rows, so it stays visible
+    /// in long listings. `None` or `Some(0)` doesn't repeat the header.
+    pub fn with_header_repeat(mut self, rows: Option<usize>) -> Self {
+        self.header_repeat = rows;
+        self.rendered.take();
+        self
+    }
+
+    /// Adds a line above the header with group labels, each one spanning
+    /// the given amount of columns, e.g. `("Location", 2)`.
+    /// Columns not covered by the groups are left with blank label.
+    pub fn with_header_groups(mut self, groups: Vec<(String, usize)>) -> Self {
+        self.header_groups = groups;
+        self.rendered.take();
+        self
+    }
+
+    /// Paints background of every other data row, starting from the second
+    /// one, so long rows are easier to follow. Borders aren't painted.
+    ///
+    /// It's applied only with `color` feature enabled, otherwise it's a no-op.
+    pub fn with_zebra(mut self, v: bool) -> Self {
+        self.zebra = v;
+        self.rendered.take();
+        self
+    }
+
+    /// Sizes each column to its widest cell, including the header and footer,
+    /// instead of dividing the table width evenly, so long values aren't
+    /// wrapped or pushed out of the borders. The table is as wide as
+    /// its columns then. [`Table::with_width`] turns it off.
+    ///
+    /// Rows are rendered from their [`RowDisplay::cells`], so options specific
+    /// to the row type, e.g. [`Table::with_si_amounts`], aren't applied.
+    pub fn with_autosize(mut self) -> Self {
+        self.autosize = true;
+        self.rendered.take();
+        self
+    }
+
+    /// Frames the table with a centered `title` and an optional `subtitle`
+    /// line below it. Both are drawn within the borders of the table above
+    /// the header, separated from it as the header is from the rows.
+    pub fn with_frame(mut self, title: impl Into<String>, subtitle: Option<&str>) -> Self {
+        self.frame = Some((title.into(), subtitle.map(String::from)));
+        self.rendered.take();
+        self
+    }
+
+    /// Shows `placeholder`, e.g. `—`, in blank cells of the data rows,
+    /// so they can't be confused with a missing column. Blank cells are
+    /// rendered as is by default.
+    pub fn with_empty_placeholder(mut self, placeholder: impl Into<String>) -> Self {
+        self.empty_placeholder = Some(placeholder.into());
+        self.rendered.take();
+        self
+    }
+
+    /// Sets the message shown in place of the rows when the table
+    /// has no data. It's `No data` by default.
+    pub fn with_empty_message(mut self, message: impl Into<String>) -> Self {
+        self.empty_message = message.into();
+        self.rendered.take();
+        self
+    }
+
+    /// Prefixes every rendered line, including borders, with `spaces` spaces.
+    /// Width of the table doesn't include the indentation.
+    pub fn with_indent(mut self, spaces: usize) -> Self {
+        self.indent = spaces;
+        self.rendered.take();
+        self
+    }
+
+    fn top_sep(&self, width: usize) -> String {
+        let g = self.style.glyphs();
+        sep(width, g.top_left, g.horizontal, g.top_right)
+    }
+
+    fn middle_sep(&self, width: usize) -> String {
+        let g = self.style.glyphs();
+        sep(width, g.middle_left, g.horizontal, g.middle_right)
+    }
+
+    fn bottom_sep(&self, width: usize) -> String {
+        let g = self.style.glyphs();
+        sep(width, g.bottom_left, g.horizontal, g.bottom_right)
+    }
+
+    /// Formats the header groups into a row of `table_width` chars where each
+    /// group is as wide as the columns it spans.
+    ///
+    /// Columns are `sized` by [`Table::with_autosize`], if it's set.
+    fn groups_row(&self, table_width: usize, sized: Option<&[usize]>) -> String {
+        let spanned: usize = self.header_groups.iter().map(|(_, n)| n).sum();
+        let columns = self
+            .header
+            .as_ref()
+            .map_or(0, |h| self.visible(h).len())
+            .max(spanned);
+        let widths = match sized {
+            Some(widths) if widths.len() == columns => widths.to_vec(),
+            _ => even_widths(table_width, columns),
+        };
+
+        let blank = (String::new(), columns - spanned);
+        let mut s = String::new();
+        let mut column = 0;
+        for (label, n) in self.header_groups.iter().chain([&blank]) {
+            if *n == 0 {
+                continue;
+            }
+            let span = widths[column..column + n].iter().sum::<usize>() + (n - 1) * 3;
+            column += n;
+            s.push_str(&format!("│ {} ", pad(label, span, Alignment::Center)));
+        }
+        s.push('│');
+        s
+    }
+
+    /// Returns width of the widest cell, including the header and footer,
+    /// of each visible column if [`Table::with_autosize`] is set.
+    fn column_widths(&self) -> Option<Vec<usize>>
+    where
+        T: RowDisplay,
+    {
+        if !self.autosize {
+            return None;
+        }
+
+        let placeholder = self.empty_placeholder.as_deref();
+        let rows = self.data.iter().map(|v| {
+            self.visible(&v.cells())
+                .iter()
+                .map(|v| match placeholder {
+                    Some(p) if v.trim().is_empty() => p.width(),
+                    _ => v.width(),
+                })
+                .collect::<Vec<_>>()
+        });
+        let labels = self.header.iter().chain(&self.footer).map(|v| {
+            self.visible(v)
+                .iter()
+                .map(|v| v.width())
+                .collect::<Vec<_>>()
+        });
+
+        let mut widths = Vec::<usize>::new();
+        for row in rows.chain(labels) {
+            if widths.len() < row.len() {
+                widths.resize(row.len(), 1);
+            }
+            for (w, v) in widths.iter_mut().zip(row) {
+                *w = (*w).max(v);
+            }
+        }
+        Some(widths)
+    }
+
+    /// Returns alignments set by [`Table::with_alignments`] or, if there are
+    /// none, the default ones of the rows, see [`RowDisplay::alignments`].
+    fn column_alignments(&self) -> Vec<Alignment>
+    where
+        T: RowDisplay,
+    {
+        match self.options.alignments.is_empty() {
+            true => self.data.first().map_or(Vec::new(), |v| v.alignments()),
+            false => self.options.alignments.clone(),
+        }
+    }
+
+    /// Returns the widest fraction, including the decimal point, of the numbers
+    /// in each visible column with [`Alignment::Decimal`] among first `rows`.
+    /// It's empty if there are no such columns.
+    fn fraction_widths(&self, alignments: &[Alignment], rows: usize) -> Vec<Option<usize>>
+    where
+        T: RowDisplay,
+    {
+        if !alignments.contains(&Alignment::Decimal) {
+            return Vec::new();
+        }
+
+        let mut widths = vec![None; alignments.len()];
+        for v in self.data.iter().take(rows) {
+            let cells = self.visible(&v.cells());
+            for (i, cell) in cells.iter().enumerate().take(alignments.len()) {
+                if alignments[i] != Alignment::Decimal {
+                    continue;
+                }
+                if let Some(w) = fraction_width(cell) {
+                    widths[i] = Some(widths[i].map_or(w, |v: usize| v.max(w)));
+                }
+            }
+        }
+        widths
+    }
+
+    /// Same as [`Table::styled`] but also paints background of the cells
+    /// of the `row` leaving its dividers as is.
+    #[cfg(feature = "color")]
+    fn striped(&self, row: String) -> String {
+        const BACKGROUND: &str = "\x1b[48;5;236m";
+        const RESET: &str = "\x1b[0m";
+
+        let vertical = self.style.glyphs().vertical.to_string();
+        row.split('\n')
+            .map(|line| {
+                // Cells are styled one by one, so that the escapes
+                // aren't replaced as control characters.
+                line.split('│')
+                    .map(|cell| match cell.is_empty() {
+                        true => String::new(),
+                        false => format!("{BACKGROUND}{}{RESET}", self.styled(cell.to_string())),
+                    })
+                    .collect::<Vec<_>>()
+                    .join(&vertical)
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[cfg(not(feature = "color"))]
+    fn striped(&self, row: String) -> String {
+        self.styled(row)
     }
 
-    fn middle_sep(&self) -> String {
-        let width = self.width - 2;
-        format!("├{:─^width$}┤", "")
+    /// Formats `text` into a single cell spanning the whole table.
+    fn centered(&self, text: &str, table_width: usize) -> String {
+        let text = pad(text, table_width.saturating_sub(4), Alignment::Center);
+        self.styled(format!("│ {} │", text))
     }
 
-    fn bottom_sep(&self) -> String {
-        let width = self.width - 2;
-        format!("└{:─^width$}┘", "")
+    /// Drops the hidden columns from `cells`.
+    fn visible<V: Clone>(&self, cells: &[V]) -> Vec<V> {
+        cells
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !self.hidden.contains(i))
+            .map(|(_, v)| v.clone())
+            .collect()
+    }
+
+    /// Same as [`Table::visible`] but keeps only the columns within `range`.
+    fn visible_range<V: Clone>(&self, cells: &[V], range: &Range<usize>) -> Vec<V> {
+        let cells = self.visible(cells);
+        let end = range.end.min(cells.len());
+        cells[range.start.min(end)..end].to_vec()
+    }
+
+    /// Replaces default `│` dividers of a row with the ones of the table style.
+    ///
+    /// Control characters, e.g. ANSI escape sequences coming from untrusted data,
+    /// are replaced with `�` so that printing the row can't mess up the terminal.
+    /// Line breaks are kept since a row may span multiple lines.
+    fn styled(&self, row: String) -> String {
+        let vertical = self.style.glyphs().vertical;
+        row.chars()
+            .map(|c| match c {
+                '│' => vertical,
+                '\n' => c,
+                c if c.is_control() => char::REPLACEMENT_CHARACTER,
+                c => c,
+            })
+            .collect()
     }
 }
 
@@ -119,44 +679,502 @@ impl<T> Table<T> {
     pub fn as_data(&self) -> &Vec<T> {
         &self.data
     }
+
+    /// Consumes the table returning its data.
+    pub fn into_data(self) -> Vec<T> {
+        self.data
+    }
+
+    /// Returns labels of the header if it's set.
+    pub fn header(&self) -> Option<&[String]> {
+        self.header.as_deref()
+    }
+
+    /// Omits columns at `cols` indexes from the rendered header, footer and rows.
+    /// The data itself is kept, so the columns may be shown again by calling
+    /// it with an empty slice.
+    ///
+    /// Rows of the table with hidden columns are rendered from
+    /// [`RowDisplay::cells`], so only column alignments are applied to them.
+    pub fn hide_columns(&mut self, cols: &[usize]) {
+        self.hidden = cols.to_vec();
+        self.rendered.take();
+    }
+
+    /// Sorts rows of the table with the comparator function.
+    /// The sort is stable.
+    pub fn sort_by(&mut self, compare: impl FnMut(&T, &T) -> Ordering) {
+        self.data.sort_by(compare);
+        self.rendered.take();
+    }
+}
+
+impl<T: RowDisplay> Table<T> {
+    /// Infers alignment of the columns from the data. Columns where every cell
+    /// is a number are aligned to the right and the rest to the left.
+    pub fn auto_align(self) -> Self {
+        let rows: Vec<Vec<String>> = self.data.iter().map(|v| v.cells()).collect();
+        let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+
+        let alignments = (0..columns)
+            .map(|i| {
+                let numeric = rows.iter().all(|cells| {
+                    cells
+                        .get(i)
+                        .is_some_and(|v| v.trim().parse::<f64>().is_ok())
+                });
+                match numeric {
+                    true => Alignment::Right,
+                    false => Alignment::Left,
+                }
+            })
+            .collect();
+        self.with_alignments(alignments)
+    }
+
+    /// Returns rows as maps of column name to cell value.
+    ///
+    /// Column names are taken from the header. If the table has no header
+    /// or the header is shorter than a row, column index is used as a name.
+    pub fn to_records(&self) -> Vec<BTreeMap<String, String>> {
+        let header = self.header.as_deref().unwrap_or_default();
+        self.data
+            .iter()
+            .map(|v| {
+                v.cells()
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, cell)| match header.get(i) {
+                        Some(name) => (name.clone(), cell),
+                        None => (i.to_string(), cell),
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Renders the table as a GitHub-flavored Markdown table.
+    ///
+    /// Cells are taken from [`RowDisplay::cells`], so width and border style
+    /// don't apply. Hidden columns are omitted, column alignments are kept.
+    /// If the table has no header, column indexes are used instead.
+    /// Footer, if any, goes as the last row.
+    pub fn to_markdown(&self) -> String {
+        let rows: Vec<Vec<String>> = self
+            .data
+            .iter()
+            .map(RowDisplay::cells)
+            .chain(self.footer.clone())
+            .map(|v| self.visible(&v))
+            .collect();
+        let header = match &self.header {
+            Some(header) => self.visible(header),
+            None => {
+                let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+                (0..columns).map(|i| i.to_string()).collect()
+            }
+        };
+        let alignments = self.visible(&self.column_alignments());
+        let divider: Vec<&str> = (0..header.len())
+            .map(|i| match alignments.get(i).copied().unwrap_or_default() {
+                Alignment::Left => ":---",
+                Alignment::Center => ":---:",
+                Alignment::Right | Alignment::Decimal => "---:",
+            })
+            .collect();
+
+        let line = |cells: &[String]| {
+            let cells: Vec<String> = cells
+                .iter()
+                .map(|v| v.replace('|', "\\|").replace('\n', " "))
+                .collect();
+            format!("| {} |\n", cells.join(" | "))
+        };
+        let mut s = line(&header);
+        s.push_str(&format!("| {} |\n", divider.join(" | ")));
+        for row in &rows {
+            s.push_str(&line(row));
+        }
+        s
+    }
 }
 
 /// This is an implementation of RowDisplay for table header.
-/// Potentially header can be something bigger then just `&'static str`,
-/// so this implementation is generic.
+/// It's generic, so it may be used for any row of displayable cells.
+///
+/// Labels that don't fit into a cell are wrapped by words, so the header may
+/// span multiple lines. Shorter labels are aligned to the top of the header.
 impl<H: Display> RowDisplay for Vec<H> {
     fn to_row(&self, table_width: usize) -> String {
-        let width = table_width / self.len() - 3;
-        let mut s = String::new();
-        for v in self {
-            s.push_str(&format!("│ {:^width$} ", v));
+        let (width, last) = cell_widths(table_width, self.len());
+        let labels: Vec<Vec<String>> = self
+            .iter()
+            .enumerate()
+            .map(|(i, v)| match i + 1 == self.len() {
+                true => wrap(&v.to_string(), last),
+                false => wrap(&v.to_string(), width),
+            })
+            .collect();
+        let height = labels.iter().map(Vec::len).max().unwrap_or(1);
+
+        let mut lines = Vec::with_capacity(height);
+        for i in 0..height {
+            let mut s = String::new();
+            for (j, label) in labels.iter().enumerate() {
+                let v = label.get(i).map(String::as_str).unwrap_or("");
+                match j + 1 == labels.len() {
+                    true => s.push_str(&format!("│ {} │", pad(v, last, Alignment::Center))),
+                    false => s.push_str(&format!("│ {} ", pad(v, width, Alignment::Center))),
+                }
+            }
+            lines.push(s);
         }
 
-        // Last column will always have 1 redundant char at the end.
-        s.pop();
-        s.push('│');
+        lines.join("\n")
+    }
 
-        s
+    fn cells(&self) -> Vec<String> {
+        self.iter().map(|v| v.to_string()).collect()
+    }
+}
+
+/// Returns width of the fractional part of a number, including the decimal
+/// point, e.g. `2` for `10.5`. It's `None` if `cell` isn't a number.
+fn fraction_width(cell: &str) -> Option<usize> {
+    cell.parse::<f64>()
+        .ok()
+        .map(|_| cell.find('.').map_or(0, |i| cell.len() - i))
+}
+
+/// Splits `text` by words into lines no longer than `width` chars.
+/// A single word longer than `width` is kept on its own line.
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::<String>::new();
+    for word in text.split_whitespace() {
+        match lines.last_mut() {
+            Some(line) if line.width() + 1 + word.width() <= width => {
+                line.push(' ');
+                line.push_str(word);
+            }
+            _ => lines.push(word.to_string()),
+        }
+    }
+
+    if lines.is_empty() {
+        lines.push(String::new());
+    }
+    lines
+}
+
+impl<T: RowDisplay> Table<T> {
+    /// Renders the table once and returns the memoized string on the next
+    /// calls. Useful when the table is printed repeatedly, e.g. every frame
+    /// of a TUI, while its data rarely changes.
+    ///
+    /// The cache is dropped whenever the table is changed, e.g. by
+    /// [`Table::sort_by`] or [`Table::with_width`].
+    pub fn render_cached(&self) -> &str {
+        self.rendered.get_or_init(|| self.to_string())
+    }
+}
+
+#[cfg(feature = "comfy")]
+impl<T: RowDisplay> Table<T> {
+    /// Converts the table into [`comfy_table::Table`] with the same header,
+    /// footer and cells of the rows. Enabled by `comfy` feature.
+    ///
+    /// Hidden columns are omitted. Width and border style aren't carried over,
+    /// use styling of `comfy_table` instead.
+    pub fn into_comfy(&self) -> comfy_table::Table {
+        let mut table = comfy_table::Table::new();
+        if let Some(header) = &self.header {
+            table.set_header(self.visible(header));
+        }
+        for v in &self.data {
+            table.add_row(self.visible(&v.cells()));
+        }
+        if let Some(footer) = &self.footer {
+            table.add_row(self.visible(footer));
+        }
+        table
     }
 }
 
 impl<T: RowDisplay> Display for Table<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let top = self.top_sep();
-        let mid = self.middle_sep();
-        let bot = self.bottom_sep();
+        self.write_to(f, None)
+    }
+}
+
+impl<T: RowDisplay> Table<T> {
+    /// Renders the table with at most `max_rows` data rows. The rest of rows
+    /// are replaced with a single `… (N more rows)` line.
+    pub fn preview(&self, max_rows: usize) -> String {
+        let mut s = String::new();
+        self.write_to(&mut s, Some(max_rows))
+            .expect("writing to String doesn't fail");
+        s
+    }
+
+    /// Renders only the columns within `range`, e.g. to page a wide table
+    /// horizontally. Indexes are of the visible columns, so hidden ones
+    /// are skipped. The header and footer are sliced the same way.
+    ///
+    /// Rows are rendered from their [`RowDisplay::cells`], so options specific
+    /// to the row type, e.g. [`Table::with_si_amounts`], aren't applied.
+    /// Header groups are dropped since they may span columns out of the range.
+    pub fn render_columns(&self, range: Range<usize>) -> String {
+        let rows = self
+            .data
+            .iter()
+            .map(|v| self.visible_range(&v.cells(), &range))
+            .collect();
+        let mut table = Table::new(rows);
+        table.header = self.header.as_ref().map(|v| self.visible_range(v, &range));
+        table.footer = self.footer.as_ref().map(|v| self.visible_range(v, &range));
+        table.width = self.width;
+        table.style = self.style;
+        table.numbering = self.numbering;
+        table.header_repeat = self.header_repeat;
+        table.indent = self.indent;
+        table.zebra = self.zebra;
+        table.empty_placeholder = self.empty_placeholder.clone();
+        table.empty_message = self.empty_message.clone();
+        table.autosize = self.autosize;
+        table.frame = self.frame.clone();
+        table.options.alignments = self.visible_range(&self.column_alignments(), &range);
+        table.to_string()
+    }
 
-        writeln!(f, "{}", top)?;
+    /// Renders the top border, the header and the separator under it, each
+    /// line terminated. Along with [`Table::render_row`] and [`Table::render_bottom`]
+    /// it allows writing rows as soon as they're read, without holding them.
+    ///
+    /// Only width, border style and hidden columns are applied.
+    pub(crate) fn render_head(&self) -> String {
+        let mut s = format!("{}\n", self.top_sep(self.width));
         if let Some(header) = &self.header {
-            writeln!(f, "{}", header.to_row(self.width))?;
+            let header = self.visible(header).to_row(self.width);
+            s.push_str(&format!("{}\n", self.styled(header)));
+            s.push_str(&format!("{}\n", self.middle_sep(self.width)));
+        }
+        s
+    }
+
+    /// Renders a single data row, terminated, see [`Table::render_head`].
+    pub(crate) fn render_row(&self, v: &T) -> String {
+        let row = match self.hidden.is_empty() {
+            true => v.to_row_with(self.width, &self.options),
+            false => self.visible(&v.cells()).to_row(self.width),
+        };
+        format!("{}\n", self.styled(row))
+    }
+
+    /// Renders the message of a table without data, terminated,
+    /// see [`Table::with_empty_message`].
+    pub(crate) fn render_empty(&self) -> String {
+        format!("{}\n", self.centered(&self.empty_message, self.width))
+    }
+
+    /// Renders the footer, if it's set, and the bottom border, terminated,
+    /// see [`Table::render_head`].
+    pub(crate) fn render_bottom(&self) -> String {
+        let mut s = String::new();
+        if let Some(footer) = &self.footer {
+            let footer = self.visible(footer).to_row(self.width);
+            s.push_str(&format!("{}\n", self.middle_sep(self.width)));
+            s.push_str(&format!("{}\n", self.styled(footer)));
+        }
+        s.push_str(&format!("{}\n", self.bottom_sep(self.width)));
+        s
+    }
+
+    /// Writes the table to `f` limiting the amount of data rows by `max_rows`.
+    fn write_to(&self, f: &mut impl Write, max_rows: Option<usize>) -> std::fmt::Result {
+        let mut lines = String::new();
+        self.write_lines(&mut lines, max_rows)?;
+
+        let mut f = Indented {
+            inner: f,
+            indent: self.indent,
+            line_start: true,
+        };
+        // The last line isn't terminated, same as the bottom border
+        f.write_str(lines.strip_suffix('\n').unwrap_or(&lines))
+    }
+
+    fn write_lines(&self, f: &mut String, max_rows: Option<usize>) -> std::fmt::Result {
+        let markdownish = self.style == BorderStyle::Markdownish;
+        let sized = self.column_widths();
+
+        // Row numbers take a cell of their own, the rest of the
+        // width is given to the rows.
+        let num_width = self
+            .numbering
+            .map_or(0, |_| self.data.len().to_string().len());
+        let num_cell = self.numbering.map_or(0, |_| num_width + 3);
+        let (table_width, width) = match &sized {
+            Some(widths) => {
+                let width = widths.iter().map(|v| v + 3).sum::<usize>() + 1;
+                (width + num_cell, width)
+            }
+            None => (self.width, self.width.saturating_sub(num_cell)),
+        };
+        let top = self.top_sep(table_width);
+        let mut mid = self.middle_sep(table_width);
+        let bot = self.bottom_sep(table_width);
+        let to_row = |cells: Vec<String>| match &sized {
+            Some(widths) => format_sized(&cells, widths, &[]),
+            None => cells.to_row(width),
+        };
+        let numbered = |number: &str, row: String| match self.numbering {
+            Some(_) => row
+                .split('\n')
+                .enumerate()
+                .map(|(i, line)| match i {
+                    0 => format!("│ {:>num_width$} {}", number, line),
+                    _ => format!("│ {:>num_width$} {}", "", line),
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+            None => row,
+        };
+
+        if markdownish {
+            // Header is underlined under each column only
+            let columns = match &self.header {
+                Some(h) => self.visible(h).len(),
+                None => self
+                    .data
+                    .first()
+                    .map_or(1, |v| self.visible(&v.cells()).len()),
+            };
+            let widths = match &sized {
+                Some(widths) => widths.clone(),
+                None => even_widths(width, columns),
+            };
+            let dashes: Vec<String> = widths.iter().map(|v| "-".repeat(*v)).collect();
+            mid = numbered("", format_sized(&dashes, &widths, &[])).replace('│', " ");
+        }
+
+        let mut header = self
+            .header
+            .as_ref()
+            .map(|h| self.styled(numbered("#", to_row(self.visible(h)))));
+        if self.header_groups.iter().any(|(_, n)| *n > 0) {
+            let groups = self.styled(numbered("", self.groups_row(width, sized.as_deref())));
+            header = Some(match header {
+                Some(header) => format!("{}\n{}", groups, header),
+                None => groups,
+            });
+        }
+
+        if !markdownish {
+            writeln!(f, "{}", top)?;
+        }
+        if let Some((title, subtitle)) = &self.frame {
+            for line in [Some(title), subtitle.as_ref()].into_iter().flatten() {
+                writeln!(f, "{}", self.centered(line, table_width))?;
+            }
+            if !markdownish {
+                writeln!(f, "{}", mid)?;
+            }
+        }
+        if let Some(header) = &header {
+            writeln!(f, "{}", header)?;
             writeln!(f, "{}", mid)?;
         }
 
-        for v in &self.data {
-            writeln!(f, "{}", v.to_row(self.width))?;
+        let shown = max_rows.unwrap_or(usize::MAX).min(self.data.len());
+        let alignments = self.visible(&self.column_alignments());
+        let fractions = self.fraction_widths(&alignments, shown);
+
+        let mut number = 0;
+        for (i, v) in self.data.iter().take(shown).enumerate() {
+            if let (Some(header), Some(n)) = (&header, self.header_repeat) {
+                if n > 0 && i > 0 && i % n == 0 {
+                    writeln!(f, "{}", mid)?;
+                    writeln!(f, "{}", header)?;
+                    writeln!(f, "{}", mid)?;
+                }
+            }
+            let label = match self.numbering {
+                Some(NumberingMode::NonEmpty) if v.is_empty() => String::new(),
+                _ => {
+                    number += 1;
+                    number.to_string()
+                }
+            };
+            let plain = self.hidden.is_empty()
+                && fractions.is_empty()
+                && self.empty_placeholder.is_none()
+                && sized.is_none();
+            let row = match plain {
+                true => v.to_row_with(width, &self.options),
+                false => {
+                    let mut cells = self.visible(&v.cells());
+                    if let Some(placeholder) = &self.empty_placeholder {
+                        cells
+                            .iter_mut()
+                            .filter(|v| v.trim().is_empty())
+                            .for_each(|v| v.clone_from(placeholder));
+                    }
+                    for (cell, fraction) in cells.iter_mut().zip(&fractions) {
+                        // Numbers with shorter fraction are padded on the right
+                        if let (Some(max), Some(own)) = (fraction, fraction_width(cell)) {
+                            cell.push_str(&" ".repeat(max - own));
+                        }
+                    }
+                    match &sized {
+                        Some(widths) => format_sized(&cells, widths, &alignments),
+                        None => format_cells(&cells, width, &alignments),
+                    }
+                }
+            };
+            let row = numbered(&label, row);
+            match self.zebra && i % 2 == 1 {
+                true => writeln!(f, "{}", self.striped(row))?,
+                false => writeln!(f, "{}", self.styled(row))?,
+            }
+        }
+
+        if shown < self.data.len() {
+            let more = format!("… ({} more rows)", self.data.len() - shown);
+            writeln!(f, "{}", self.centered(&more, table_width))?;
+        }
+        if self.data.is_empty() {
+            writeln!(f, "{}", self.centered(&self.empty_message, table_width))?;
+        }
+
+        if let Some(footer) = &self.footer {
+            writeln!(f, "{}", mid)?;
+            let footer = to_row(self.visible(footer));
+            writeln!(f, "{}", self.styled(numbered("", footer)))?;
         }
 
-        write!(f, "{}", bot)?;
+        if !markdownish {
+            write!(f, "{}", bot)?;
+        }
+        Ok(())
+    }
+}
+
+/// Writer prefixing every line with `indent` spaces.
+struct Indented<'a, W> {
+    inner: &'a mut W,
+    indent: usize,
+    line_start: bool,
+}
+
+impl<W: Write> Write for Indented<'_, W> {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        for line in s.split_inclusive('\n') {
+            if self.line_start {
+                write!(self.inner, "{:1$}", "", self.indent)?;
+            }
+            self.inner.write_str(line)?;
+            self.line_start = line.ends_with('\n');
+        }
         Ok(())
     }
 }
@@ -166,15 +1184,21 @@ mod tests {
     use super::*;
     use crate::model::Item;
 
+    fn item(id: u32, name: &str, item_type: &str, condition: &str) -> Item {
+        Item {
+            id,
+            name: name.into(),
+            item_type: item_type.into(),
+            condition: condition.into(),
+            amount: 10,
+            added: None,
+            extra: Default::default(),
+        }
+    }
+
     #[test]
     fn table_with_header() {
-        let item = Item {
-            id: 1,
-            name: "Test".into(),
-            item_type: "Test".into(),
-            condition: "Good".into(),
-            amount: 10,
-        };
+        let item = item(1, "Test", "Test", "Good");
         let header = vec!["ID", "NAME", "TYPE", "CONDITION", "AMOUNT"];
         let items = vec![item];
 
@@ -202,13 +1226,7 @@ mod tests {
 
     #[test]
     fn table_without_header() {
-        let item = Item {
-            id: 1,
-            name: "Test".into(),
-            item_type: "Test".into(),
-            condition: "Good".into(),
-            amount: 10,
-        };
+        let item = item(1, "Test", "Test", "Good");
         let table = Table::new(vec![item]);
 
         assert_eq!(table.data.len(), 1);
@@ -222,4 +1240,542 @@ mod tests {
         assert_eq!(rows.len(), 2 + 1);
         println!("{}", table);
     }
+
+    #[test]
+    fn table_rounded_style() {
+        let item = item(1, "Test", "Test", "Good");
+        let table = Table::new(vec![item])
+            .with_header(vec!["ID", "NAME", "TYPE", "CONDITION", "AMOUNT"])
+            .with_style(BorderStyle::Rounded);
+
+        let table_string = format!("{}", table);
+
+        assert!(table_string.starts_with('╭'));
+        assert!(table_string.ends_with('╯'));
+        println!("{}", table_string);
+    }
+
+    #[test]
+    fn table_wrapped_header() {
+        let item = item(1, "Test", "Test", "Good");
+        let table = Table::new(vec![item])
+            .with_header(vec!["ID", "NAME", "TYPE", "CONDITION", "QUANTITY IN STOCK"])
+            .with_width(60);
+
+        let table_string = format!("{}", table);
+        let rows: Vec<&str> = table_string.split("\n").collect();
+
+        // 2 - header lines
+        // 1 - item
+        // 2 - top / bottom separators
+        // 1 - middle separator
+        assert_eq!(rows.len(), 2 + 1 + 2 + 1);
+        assert!(rows[1].contains("QUANTITY"));
+        assert!(rows[2].contains("IN STOCK"));
+        assert!(rows[3].starts_with('├'));
+
+        let dividers = |row: &str| -> Vec<usize> {
+            row.chars()
+                .enumerate()
+                .filter_map(|(i, c)| (c == '│').then_some(i))
+                .collect()
+        };
+        assert_eq!(dividers(rows[1]), dividers(rows[4]));
+        assert_eq!(dividers(rows[2]), dividers(rows[4]));
+
+        println!("{}", table_string);
+    }
+
+    #[test]
+    fn table_last_column_aligned() {
+        let item = item(1, "Test", "Test", "Good");
+        let table = Table::new(vec![item])
+            .with_header(vec!["ID", "NAME", "TYPE", "CONDITION", "AMOUNT"])
+            .with_width(93);
+
+        let table_string = format!("{}", table);
+        let rows: Vec<&str> = table_string.split("\n").collect();
+
+        let last_cell = |row: &str| row.rsplit('│').nth(1).unwrap().chars().count();
+        assert_eq!(last_cell(rows[1]), last_cell(rows[3]));
+        for row in rows {
+            assert_eq!(row.chars().count(), 93);
+        }
+    }
+
+    #[test]
+    fn table_to_records() {
+        let item = item(1, "Test", "Tool", "Good");
+        let table =
+            Table::new(vec![item]).with_header(vec!["ID", "NAME", "TYPE", "CONDITION", "AMOUNT"]);
+
+        let records = table.to_records();
+
+        assert_eq!(records.len(), 1);
+        assert_eq!(
+            records[0].keys().collect::<Vec<_>>(),
+            vec!["AMOUNT", "CONDITION", "ID", "NAME", "TYPE"]
+        );
+        assert_eq!(records[0]["ID"], "1");
+        assert_eq!(records[0]["NAME"], "Test");
+        assert_eq!(records[0]["TYPE"], "Tool");
+        assert_eq!(records[0]["CONDITION"], "Good");
+        assert_eq!(records[0]["AMOUNT"], "10");
+    }
+
+    #[test]
+    fn table_row_numbers_non_empty() {
+        let rows = vec![vec!["One", "Two"], vec!["", ""], vec!["Three", "Four"]];
+        let table = Table::new(rows)
+            .with_header(vec!["COL1", "COL2"])
+            .with_width(40)
+            .with_row_numbers(NumberingMode::NonEmpty);
+
+        let table_string = format!("{}", table);
+        let rows: Vec<&str> = table_string.split("\n").collect();
+
+        assert!(rows[1].starts_with("│ # │"));
+        assert!(rows[3].starts_with("│ 1 │"));
+        assert!(rows[4].starts_with("│   │"));
+        assert!(rows[5].starts_with("│ 2 │"));
+        for row in rows {
+            assert_eq!(row.chars().count(), 40);
+        }
+        println!("{}", table_string);
+    }
+
+    #[test]
+    fn table_escapes_control_chars() {
+        let item = item(1, "\x1b[31mRed", "Tool\r", "Good");
+        let table =
+            Table::new(vec![item]).with_header(vec!["ID", "NAME", "TYPE", "CONDITION", "AMOUNT"]);
+
+        let table_string = format!("{}", table);
+
+        assert!(!table_string.contains('\x1b'));
+        assert!(!table_string.contains('\r'));
+        assert!(table_string.contains("�[31mRed"));
+    }
+
+    #[test]
+    fn table_header_accessor() {
+        let table = Table::new(vec![vec!["One", "Two"]]).with_header(vec!["COL1", "COL2"]);
+        assert_eq!(
+            table.header(),
+            Some(&["COL1".to_string(), "COL2".to_string()][..])
+        );
+
+        let table = Table::new(vec![vec!["One", "Two"]]);
+        assert_eq!(table.header(), None);
+    }
+
+    #[test]
+    fn table_auto_align() {
+        let item = item(1, "Test", "Tool", "Good");
+        let table = Table::new(vec![item])
+            .with_header(vec!["ID", "NAME", "TYPE", "CONDITION", "AMOUNT"])
+            .auto_align();
+
+        let table_string = format!("{}", table);
+        let row = table_string.split("\n").nth(3).unwrap();
+        let cells: Vec<&str> = row.split('│').collect();
+
+        assert!(cells[2].starts_with(" Test "));
+        assert!(cells[5].ends_with(" 10 "));
+        println!("{}", table_string);
+    }
+
+    #[test]
+    fn table_header_repeat() {
+        let items: Vec<Item> = (1..=5).map(|id| item(id, "Test", "Test", "Good")).collect();
+
+        let table = Table::new(items)
+            .with_header(vec!["ID", "NAME", "TYPE", "CONDITION", "AMOUNT"])
+            .with_header_repeat(Some(2));
+        let s = table.to_string();
+
+        // Initial header and repeats before the 3rd and 5th rows
+        assert_eq!(s.matches("CONDITION").count(), 3);
+    }
+
+    #[test]
+    fn table_render_cached() {
+        let mut table = Table::new(vec![vec!["b"], vec!["a"]]).with_width(20);
+
+        let first = table.render_cached() as *const str;
+        let second = table.render_cached() as *const str;
+        assert_eq!(first, second);
+        assert_eq!(table.render_cached(), table.to_string());
+
+        table.sort_by(|a, b| a.cmp(b));
+        assert_eq!(table.render_cached(), table.to_string());
+
+        // The cache keeps the table shareable across threads
+        fn assert_sync<T: Sync>(_: &T) {}
+        assert_sync(&table);
+    }
+
+    #[test]
+    fn table_with_indent() {
+        let table = Table::new(vec![vec!["One", "Two"]])
+            .with_header(vec!["A", "B"])
+            .with_footer(vec!["C", "D"])
+            .with_width(20)
+            .with_indent(4);
+        let s = table.to_string();
+
+        assert_eq!(s.lines().count(), 7);
+        for line in s.lines() {
+            assert!(line.starts_with("    "));
+            assert!(!line.starts_with("     "));
+        }
+    }
+
+    #[test]
+    fn table_hide_columns() {
+        let items: Vec<Item> = ["Tool", "Fasteners"]
+            .into_iter()
+            .enumerate()
+            .map(|(id, item_type)| item(id as u32, "Test", item_type, "Good"))
+            .collect();
+
+        let mut table =
+            Table::new(items).with_header(vec!["ID", "NAME", "TYPE", "CONDITION", "AMOUNT"]);
+        table.hide_columns(&[2]);
+        let s = table.to_string();
+
+        for line in s.lines() {
+            assert!(!line.contains("TYPE"));
+            assert!(!line.contains("Tool"));
+            assert!(!line.contains("Fasteners"));
+        }
+        assert!(s.contains("CONDITION"));
+        // Header and rows have the same amount of dividers
+        let dividers: Vec<usize> = s
+            .lines()
+            .filter(|l| l.starts_with('│'))
+            .map(|l| l.matches('│').count())
+            .collect();
+        assert_eq!(dividers, vec![5; 3]);
+    }
+
+    #[test]
+    fn table_preview() {
+        let rows: Vec<Vec<String>> = (1..=5).map(|v| vec![v.to_string()]).collect();
+        let table = Table::new(rows).with_header(vec!["N"]).with_width(30);
+        let s = table.preview(2);
+
+        let lines: Vec<&str> = s.lines().collect();
+        assert_eq!(lines.len(), 7);
+        assert!(lines[5].contains("… (3 more rows)"));
+        assert_eq!(lines[5].chars().count(), 30);
+        assert!(!s.contains(" 3 "));
+
+        assert_eq!(table.preview(5), table.to_string());
+    }
+
+    #[test]
+    fn table_header_groups() {
+        let table = Table::new(vec![vec!["1", "Hummer", "Tool", "10"]])
+            .with_header(vec!["ID", "NAME", "TYPE", "AMOUNT"])
+            .with_header_groups(vec![("".into(), 1), ("Location".into(), 2)])
+            .with_width(41);
+        let s = table.to_string();
+        let lines: Vec<&str> = s.lines().collect();
+
+        // Group divider lines up with the dividers of its member columns
+        let dividers = |line: &str| -> Vec<usize> {
+            line.chars()
+                .enumerate()
+                .filter(|(_, c)| *c == '│')
+                .map(|(i, _)| i)
+                .collect()
+        };
+        let group = dividers(lines[1]);
+        let header = dividers(lines[2]);
+        assert_eq!(group, vec![header[0], header[1], header[3], header[4]]);
+        assert!(lines[1].contains("Location"));
+        assert_eq!(lines[1].chars().count(), 41);
+    }
+
+    #[test]
+    fn table_narrow_width() {
+        let item = item(1, "Test", "Test", "Good");
+        let table = Table::new(vec![item])
+            .with_header(vec!["ID", "NAME", "TYPE", "CONDITION", "AMOUNT"])
+            .with_width(10);
+        assert!(table.to_string().contains("Test"));
+
+        assert_eq!(cell_widths(10, 5), (1, 1));
+        assert_eq!(cell_widths(100, 5), (17, 16));
+    }
+
+    #[test]
+    fn table_markdownish_style() {
+        let table = Table::new(vec![vec!["1", "Hummer"], vec!["2", "Nails"]])
+            .with_header(vec!["ID", "NAME"])
+            .with_style(BorderStyle::Markdownish)
+            .with_width(20);
+        let s = table.to_string();
+        let lines: Vec<&str> = s.lines().collect();
+
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].contains("ID") && lines[0].contains("NAME"));
+        assert!(lines[1].chars().all(|c| c == '-' || c == ' '));
+        assert!(!s.ends_with('\n'));
+
+        // Dashes are under the cells and spaces are under the dividers
+        let bordered = Table::new(vec![vec!["1", "Hummer"]])
+            .with_header(vec!["ID", "NAME"])
+            .with_width(20)
+            .to_string();
+        let header = bordered.lines().nth(1).unwrap();
+        assert_eq!(lines[1].chars().count(), header.chars().count());
+        for (u, h) in lines[1].chars().zip(header.chars()) {
+            if h == '│' {
+                assert_eq!(u, ' ');
+            }
+        }
+        assert!(lines[1].contains("-------"));
+    }
+
+    #[test]
+    fn table_render_columns() {
+        let item = item(1, "Hummer", "Tool", "Mint");
+        let table = Table::new(vec![item])
+            .with_header(vec!["ID", "NAME", "TYPE", "CONDITION", "AMOUNT"])
+            .with_width(40);
+        let s = table.render_columns(1..3);
+
+        assert!(s.contains("NAME") && s.contains("TYPE"));
+        assert!(s.contains("Hummer") && s.contains("Tool"));
+        for v in ["ID", "CONDITION", "AMOUNT", "Mint", "10"] {
+            assert!(!s.contains(v), "{v} is out of the range");
+        }
+        assert!(s.lines().all(|v| v.chars().count() == 40));
+
+        // Range past the last column is cut
+        assert!(table.render_columns(4..10).contains("AMOUNT"));
+    }
+
+    #[test]
+    fn table_frame() {
+        let table = Table::new(vec![vec!["1", "Hummer"]])
+            .with_header(vec!["ID", "NAME"])
+            .with_width(30)
+            .with_frame("Inventory", Some("Shelter #1"));
+        let s = table.to_string();
+        let lines: Vec<&str> = s.lines().collect();
+
+        // top, title, subtitle, mid, header, mid, row, bottom
+        assert_eq!(lines.len(), 8);
+        assert!(lines[0].starts_with('┌'));
+        assert!(lines[3].starts_with('├'));
+        for (line, text) in [(lines[1], "Inventory"), (lines[2], "Shelter #1")] {
+            assert_eq!(line.chars().count(), 30);
+            let inner = line.trim_matches('│');
+            let left = inner.len() - inner.trim_start().len();
+            let right = inner.len() - inner.trim_end().len();
+            assert_eq!(inner.trim(), text);
+            assert!(left.abs_diff(right) <= 1);
+        }
+
+        let table = Table::new(vec![vec!["1"]]).with_frame("Inventory", None);
+        assert_eq!(table.to_string().lines().count(), 5);
+    }
+
+    #[test]
+    fn table_empty_message() {
+        let table = Table::<Item>::new(Vec::new())
+            .with_header(vec!["ID", "NAME", "TYPE", "CONDITION", "AMOUNT"])
+            .with_width(40);
+        let s = table.to_string();
+        let rows: Vec<&str> = s.lines().collect();
+
+        // top, header, mid, message, bottom
+        assert_eq!(rows.len(), 5);
+        assert!(rows[2].starts_with('├'));
+        assert_eq!(rows[3], format!("│ {:^36} │", "No data"));
+        assert!(rows[4].starts_with('└'));
+
+        let s = table.with_empty_message("Nothing found").to_string();
+        assert!(s.contains("Nothing found") && !s.contains("No data"));
+    }
+
+    #[test]
+    fn table_empty_placeholder() {
+        let item = item(1, "", "Tool", "Mint");
+        let table = Table::new(vec![item])
+            .with_width(50)
+            .with_empty_placeholder("—");
+        let s = table.to_string();
+        let row = s.lines().nth(1).unwrap();
+        let cells: Vec<&str> = row.split('│').map(str::trim).collect();
+
+        assert_eq!(cells[1..6], ["1", "—", "Tool", "Mint", "10"]);
+    }
+
+    #[test]
+    fn table_autosize() {
+        let name = "Extremely long name of a rusty but still usable garden saw";
+        let item = item(1, name, "Tool", "Mint");
+        let table = Table::new(vec![item])
+            .with_header(vec!["ID", "NAME", "TYPE", "CONDITION", "AMOUNT"])
+            .with_autosize();
+        let s = table.to_string();
+        let lines: Vec<&str> = s.lines().collect();
+
+        // top, header, mid, item, bottom
+        assert_eq!(lines.len(), 5);
+        assert!(lines[3].contains(&format!("│ {} │", name)));
+        assert!(lines[1].contains("│ CONDITION │"));
+        let width = lines[0].chars().count();
+        assert!(lines.iter().all(|v| v.chars().count() == width));
+
+        // Explicit width falls back to the even columns
+        let table = table.with_width(40);
+        assert_eq!(
+            table.to_string().lines().next().unwrap().chars().count(),
+            40
+        );
+    }
+
+    #[test]
+    fn table_wide_chars() {
+        let item = item(1, "のこぎり", "Säge", "Mint");
+        let table = Table::new(vec![item])
+            .with_header(vec!["ID", "名前", "TYPE", "CONDITION", "AMOUNT"])
+            .with_width(60);
+        let s = table.to_string();
+
+        for line in s.lines() {
+            assert_eq!(line.width(), 60, "{line}");
+        }
+    }
+
+    #[test]
+    fn table_bincode_round_trip() {
+        let item = Item {
+            added: chrono::NaiveDate::from_ymd_opt(2024, 4, 1),
+            extra: [("shelf".to_string(), "B".to_string())].into(),
+            ..item(1, "Hummer", "Tool", "Requires maintenance")
+        };
+        let table = Table::new(vec![item])
+            .with_header(vec!["ID", "NAME", "TYPE", "CONDITION", "AMOUNT"])
+            .with_alignments(vec![Alignment::Right])
+            .with_width(60);
+        let rendered = table.render_cached().to_string();
+
+        let bytes = bincode::serialize(&table).unwrap();
+        let restored: Table<Item> = bincode::deserialize(&bytes).unwrap();
+
+        assert_eq!(restored.as_data(), table.as_data());
+        assert_eq!(restored.header, table.header);
+        assert_eq!(restored.to_string(), rendered);
+    }
+
+    #[test]
+    fn table_to_markdown() {
+        let table = Table::new(vec![vec!["1", "Hummer"], vec!["2", "Saw | Axe"]])
+            .with_header(vec!["ID", "NAME"])
+            .with_alignments(vec![Alignment::Right, Alignment::Left]);
+
+        assert_eq!(
+            table.to_markdown(),
+            "| ID | NAME |\n\
+             | ---: | :--- |\n\
+             | 1 | Hummer |\n\
+             | 2 | Saw \\| Axe |\n"
+        );
+
+        let mut table = Table::new(vec![vec!["1", "Hummer", "Tool"]]);
+        table.hide_columns(&[1]);
+        assert_eq!(
+            table.to_markdown(),
+            "| 0 | 1 |\n| :---: | :---: |\n| 1 | Tool |\n"
+        );
+    }
+
+    #[test]
+    fn table_owned_header() {
+        let columns: Vec<String> = "id,name".split(',').map(|v| v.to_uppercase()).collect();
+        let table = Table::new(vec![vec!["1", "Hummer"]]).with_header(columns);
+
+        assert_eq!(
+            table.header(),
+            Some(&["ID".to_string(), "NAME".to_string()][..])
+        );
+        assert_eq!(
+            table.to_string(),
+            Table::new(vec![vec!["1", "Hummer"]])
+                .with_header(["ID", "NAME"])
+                .to_string()
+        );
+    }
+
+    #[cfg(feature = "comfy")]
+    #[test]
+    fn table_into_comfy() {
+        let table = Table::new(vec![
+            vec!["1", "Hummer", "Tool"],
+            vec!["2", "Nails", "Fasteners"],
+        ])
+        .with_header(vec!["ID", "NAME", "TYPE"]);
+        let mut comfy = table.into_comfy();
+
+        assert_eq!(comfy.row_count(), 2);
+        assert_eq!(comfy.column_count(), 3);
+        assert!(comfy.header().is_some());
+    }
+
+    #[test]
+    fn table_decimal_alignment() {
+        let rows = vec![
+            vec!["Saw", "2"],
+            vec!["Nails", "10.5"],
+            vec!["Box", "400.25"],
+        ];
+        let table = Table::new(rows)
+            .with_alignments(vec![Alignment::Left, Alignment::Decimal])
+            .with_width(30);
+        let s = table.to_string();
+
+        // Integer numbers end where the decimal point would be
+        let points: Vec<usize> = s
+            .lines()
+            .filter(|line| line.starts_with('│'))
+            .map(|line| {
+                let line = line.trim_end_matches('│').trim_end();
+                line.find('.').unwrap_or(line.len())
+            })
+            .collect();
+        assert_eq!(points.len(), 3);
+        assert!(points.iter().all(|v| *v == points[0]));
+    }
+
+    #[cfg(feature = "color")]
+    #[test]
+    fn table_zebra() {
+        let rows: Vec<Vec<String>> = (1..=4).map(|v| vec![v.to_string(), "x".into()]).collect();
+        let table = Table::new(rows)
+            .with_header(vec!["N", "X"])
+            .with_width(20)
+            .with_zebra(true);
+        let s = table.to_string();
+        let lines: Vec<&str> = s.lines().collect();
+
+        let painted: Vec<bool> = lines[3..7]
+            .iter()
+            .map(|l| l.contains("\x1b[48;5;236m"))
+            .collect();
+        assert_eq!(painted, vec![false, true, false, true]);
+        assert!(!lines[1].contains('\x1b'));
+
+        // Borders stay uncolored and visible width is unchanged
+        let plain = lines[4]
+            .replace("\x1b[48;5;236m", "")
+            .replace("\x1b[0m", "");
+        assert_eq!(plain, lines[3].replace('1', "2"));
+        assert!(lines[4].starts_with('│') && lines[4].ends_with('│'));
+    }
 }
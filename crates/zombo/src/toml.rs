@@ -0,0 +1,93 @@
+//! Reading items from TOML documents, enabled by `toml` feature.
+//!
+//! The document has a top-level `items` array of tables, each of them
+//! with the same fields as the columns of CSV data.
+//!
+//! ```toml
+//! [[items]]
+//! id = 1
+//! name = "Hummer"
+//! type = "Tool"
+//! condition = "Mint"
+//! amount = 10
+//! ```
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use zombo::Zomboid;
+//!
+//! let mut z = Zomboid::new(zombo::toml::reader("path/to/data.toml").unwrap());
+//! let table = z.stream().unwrap();
+//! ```
+use crate::model::Item;
+use ::toml::de::Error;
+use ::toml::{Table, Value};
+use serde::Deserialize;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Reads TOML document at `path` and returns an iterator deserializing
+/// an [`Item`] from each table of its `items` array.
+///
+/// The whole document is parsed up front, a malformed one or one without
+/// `items` array is reported as [`io::ErrorKind::InvalidData`].
+pub fn reader(path: impl AsRef<Path>) -> io::Result<impl Iterator<Item = Result<Item, Error>>> {
+    from_str(&fs::read_to_string(path)?)
+}
+
+/// Same as [`reader`] but parses the document from `s`.
+pub fn from_str(s: &str) -> io::Result<impl Iterator<Item = Result<Item, Error>>> {
+    let mut doc: Table = s
+        .parse()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    let items = match doc.remove("items") {
+        Some(Value::Array(items)) => items,
+        _ => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "missing `items` array",
+            ))
+        }
+    };
+    Ok(items.into_iter().map(Item::deserialize))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Zomboid;
+
+    #[test]
+    fn from_str() {
+        let data = r#"
+            [[items]]
+            id = 1
+            name = "Hummer"
+            type = "Tool"
+            condition = "Mint"
+            amount = 10
+
+            [[items]]
+            id = 2
+            name = "Nails"
+            type = "Fasteners"
+            condition = "Good"
+            amount = 400
+            shelf = "B2"
+        "#;
+        let mut z = Zomboid::new(super::from_str(data).unwrap());
+        let table = z.stream().unwrap();
+        let items = table.as_data();
+
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].name, "Hummer");
+        assert_eq!(items[0].condition, "Mint");
+        assert_eq!(items[1].amount, 400);
+        assert_eq!(items[1].extra["shelf"], "B2");
+
+        assert!(super::from_str("[[other]]\nid = 1\n").is_err());
+        let mut it = super::from_str("[[items]]\nid = 1\n").unwrap();
+        assert!(it.next().unwrap().is_err());
+    }
+}